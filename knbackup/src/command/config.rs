@@ -0,0 +1,135 @@
+use clap::{Parser, Subcommand};
+
+use crate::{
+    command::download::DownloadArgs,
+    kidsnote::{DownloadConfig, KnBackupConfig},
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+
+    /// Profile name in the config file
+    #[arg(
+        long = "profile",
+        env = "KNB_PROFILE",
+        value_name = "Profile Name",
+        global = true
+    )]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml",
+        global = true
+    )]
+    pub config_path: String,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigAction {
+    /// Show the effective download options and where each value comes from
+    Show,
+    /// Print the effective value of a download option
+    Get {
+        /// client_id|output_dir|date_start|date_end|test|report_card|archive_raw
+        key: String,
+    },
+    /// Save a download option ([download], or the profile with --profile). Omit the value to remove it.
+    Set {
+        /// client_id|output_dir|date_start|date_end|test|report_card|archive_raw
+        key: String,
+        value: Option<String>,
+    },
+}
+
+pub struct ConfigCommand {}
+
+impl ConfigCommand {
+    pub async fn run(args: &ConfigArgs) -> RunSummary {
+        let mut summary = RunSummary::new("config");
+        let result = match &args.action {
+            ConfigAction::Show => Self::show(args),
+            ConfigAction::Get { key } => Self::get(args, key),
+            ConfigAction::Set { key, value } => Self::set(args, key, value.as_deref()),
+        };
+        if let Err(err) = result {
+            log::error!(target:"config", "{}", err);
+            summary.error(err);
+        }
+        summary
+    }
+
+    /// 우선 순위대로 나열한 download 옵션 (환경 변수 > 계정별 > [download] > 기본값)
+    fn layers(args: &ConfigArgs) -> Result<Vec<(&'static str, DownloadConfig)>, String> {
+        let config = KnBackupConfig::from_file(&args.config_path).map_err(|err| err.to_string())?;
+        // 명령행 없이 읽으면 환경 변수 값만 남는다.
+        let env_args = DownloadArgs::try_parse_from(["download"])
+            .map_err(|err| format!("env read fail. {}", err))?;
+        let profile_config = config
+            .profile(args.profile.as_deref())
+            .map(|f| f.download.clone())
+            .unwrap_or_default();
+        Ok(vec![
+            ("env", env_args.to_config()),
+            ("profile", profile_config),
+            ("global", config.download),
+            ("default", DownloadConfig::defaults()),
+        ])
+    }
+
+    fn effective(
+        layers: &[(&'static str, DownloadConfig)],
+        key: &str,
+    ) -> Result<Option<(String, &'static str)>, String> {
+        for (source, config) in layers {
+            if let Some(value) = config.get(key).map_err(|err| err.to_string())? {
+                return Ok(Some((value, source)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn show(args: &ConfigArgs) -> Result<(), String> {
+        let layers = Self::layers(args)?;
+        let mut lines = vec![
+            format!("config = {}", args.config_path),
+            format!("profile = {}", args.profile.as_deref().unwrap_or("default")),
+        ];
+        for key in DownloadConfig::KEYS {
+            match Self::effective(&layers, key)? {
+                Some((value, source)) => lines.push(format!("{} = {} ({})", key, value, source)),
+                None => lines.push(format!("{} =", key)),
+            }
+        }
+        println!("{}", lines.join("\n"));
+        Ok(())
+    }
+
+    fn get(args: &ConfigArgs, key: &str) -> Result<(), String> {
+        let layers = Self::layers(args)?;
+        let value = Self::effective(&layers, key)?.map(|(value, _)| value);
+        println!("{}", value.unwrap_or_default());
+        Ok(())
+    }
+
+    fn set(args: &ConfigArgs, key: &str, value: Option<&str>) -> Result<(), String> {
+        let mut config =
+            KnBackupConfig::from_file(&args.config_path).map_err(|err| err.to_string())?;
+        let download = match &args.profile {
+            Some(name) => match config.profiles.get_mut(name) {
+                Some(profile) => &mut profile.download,
+                None => return Err(format!("profile not found. login first. profile={}", name)),
+            },
+            None => &mut config.download,
+        };
+        download.set(key, value).map_err(|err| err.to_string())?;
+        config
+            .save(args.config_path.clone())
+            .map_err(|err| err.to_string())
+    }
+}
@@ -1,17 +1,29 @@
-use chrono::{DateTime, TimeZone, Utc};
-use clap::Parser;
-use filetime::FileTime;
-use kidsnote_sdk::{
-    auth::error_types::AuthError, child::datatypes::GetReportsParam, options::KidsnoteOptions,
-    resource::datatypes::ResourceImageResponse, tool::image_tool::ImageTool,
-    user::datatypes::MeInfoResponse, KidsnoteSdk,
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
 };
 
-use std::{
-    collections::HashMap, path::{Path, PathBuf}, time::Duration
+use clap::{builder::BoolishValueParser, Parser};
+use kidsnote_sdk::{
+    archive::ResponseArchive,
+    auth::error_types::AuthError,
+    backup::{
+        datatypes::BackupSink,
+        event::{BackupEvent, BackupEventHandler},
+        BackupJob,
+    },
+    options::KidsnoteOptions,
+    tool::render_theme::RenderTheme,
+    user::datatypes::MeInfoResponse,
+    KidsnoteSdk,
 };
 
-use crate::kidsnote::{KidsnoteConfigProfile, KnBackupConfig};
+use crate::{
+    kidsnote::{DownloadConfig, KidsnoteConfigProfile, KnBackupConfig, TokenStorage},
+    lock::OutputLock,
+    state::BackupState,
+    summary::RunSummary,
+};
 
 #[derive(Parser, Debug, Clone)]
 pub struct DownloadArgs {
@@ -46,6 +58,10 @@ pub struct DownloadArgs {
     #[arg(short = 'r', long = "refresh-token")]
     pub refresh_token: Option<String>,
 
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
     // Sets a custom config file
     #[arg(
         long = "config",
@@ -55,23 +71,74 @@ pub struct DownloadArgs {
     pub config_path: String,
 
     /// Backup start date
-    #[arg(long, alias = "ds", value_name = "Start Date")]
+    #[arg(long, alias = "ds", env = "KNB_DATE_START", value_name = "Start Date")]
     pub date_start: Option<String>,
 
     /// Backup end date
-    #[arg(long, alias = "de", value_name = "End Date")]
+    #[arg(long, alias = "de", env = "KNB_DATE_END", value_name = "End Date")]
     pub date_end: Option<String>,
 
+    /// Output path [default: ./output]
     #[arg(
         short = 'o',
         long = "output-path",
-        value_name = "Output Path",
-        default_value = "./output"
+        env = "KNB_OUTPUT_PATH",
+        value_name = "Output Path"
+    )]
+    pub output_dir: Option<String>,
+
+    /// Dry run. list what would be downloaded
+    #[arg(
+        short = 't',
+        long = "test",
+        env = "KNB_TEST",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new(),
+        overrides_with = "no_test"
+    )]
+    pub test: Option<bool>,
+
+    /// Turn off --test (overrides the environment and the config file)
+    #[arg(long = "no-test", overrides_with = "test")]
+    pub no_test: bool,
+
+    /// Also render a report card image with the report's photos
+    #[arg(
+        long = "report-card",
+        env = "KNB_REPORT_CARD",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new(),
+        overrides_with = "no_report_card"
+    )]
+    pub report_card: Option<bool>,
+
+    /// Turn off --report-card (overrides the environment and the config file)
+    #[arg(long = "no-report-card", overrides_with = "report_card")]
+    pub no_report_card: bool,
+
+    /// Keep every raw API response (gzip) in <output>/.knbackup/raw
+    #[arg(
+        long = "archive-raw",
+        env = "KNB_ARCHIVE_RAW",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new(),
+        overrides_with = "no_archive_raw"
     )]
-    pub output_dir: String,
+    pub archive_raw: Option<bool>,
 
-    #[arg(short = 't', long = "test")]
-    pub test: bool,
+    /// Turn off --archive-raw (overrides the environment and the config file)
+    #[arg(long = "no-archive-raw", overrides_with = "archive_raw")]
+    pub no_archive_raw: bool,
+
+    /// Download with every profile in the config file
+    #[arg(long = "all-profiles", conflicts_with = "profile")]
+    pub all_profiles: bool,
 }
 
 impl DownloadArgs {
@@ -81,15 +148,22 @@ impl DownloadArgs {
             user_id: None,
             user_pass: None,
             refresh_token: None,
+            profile: None,
             config_path: "~/.knbackup/config.toml".to_string(),
             date_start: None,
             date_end: None,
-            output_dir: "./output".to_string(),
-            test: false,
+            output_dir: None,
+            test: None,
+            no_test: false,
+            report_card: None,
+            no_report_card: false,
+            archive_raw: None,
+            no_archive_raw: false,
+            all_profiles: false,
         }
     }
 
-    ///
+    /// config 의 로그인 정보로 비어있는 값을 채운다.
     fn update_profile(&mut self, profile: &KidsnoteConfigProfile) {
         if self.refresh_token.is_none() && profile.refresh_token.is_some() {
             self.refresh_token = profile.refresh_token.clone();
@@ -98,6 +172,69 @@ impl DownloadArgs {
             self.user_id = profile.user_id.clone();
         }
     }
+
+    /// config 의 download 옵션으로 비어있는 값을 채운다.
+    /// - 명령행과 환경 변수 값이 config 보다 우선한다. (--no-test 처럼 끌 수도 있다)
+    pub(crate) fn apply_config(&mut self, config: &DownloadConfig) {
+        let args = self.to_config();
+        let config = config.clone().or(&DownloadConfig::defaults());
+        self.client_id = self.client_id.take().or(config.client_id);
+        self.output_dir = self.output_dir.take().or(config.output_dir);
+        self.date_start = self.date_start.take().or(config.date_start);
+        self.date_end = self.date_end.take().or(config.date_end);
+        self.test = args.test.or(config.test);
+        self.report_card = args.report_card.or(config.report_card);
+        self.archive_raw = args.archive_raw.or(config.archive_raw);
+        (self.no_test, self.no_report_card, self.no_archive_raw) = (false, false, false);
+    }
+
+    /// 명령행과 환경 변수로 지정한 download 옵션
+    pub(crate) fn to_config(&self) -> DownloadConfig {
+        // --no-xxx 는 false
+        let flag = |value: Option<bool>, off: bool| if off { Some(false) } else { value };
+        DownloadConfig {
+            client_id: self.client_id.clone(),
+            output_dir: self.output_dir.clone(),
+            date_start: self.date_start.clone(),
+            date_end: self.date_end.clone(),
+            test: flag(self.test, self.no_test),
+            report_card: flag(self.report_card, self.no_report_card),
+            archive_raw: flag(self.archive_raw, self.no_archive_raw),
+        }
+    }
+
+    /// 받지 않고 목록만 확인
+    pub fn is_test(&self) -> bool {
+        self.to_config().test.unwrap_or(false)
+    }
+
+    /// 알림장 카드 이미지도 만든다
+    pub fn is_report_card(&self) -> bool {
+        self.to_config().report_card.unwrap_or(false)
+    }
+
+    /// API 응답 원문 보관
+    pub fn is_archive_raw(&self) -> bool {
+        self.to_config().archive_raw.unwrap_or(false)
+    }
+
+    pub fn output_dir(&self) -> &str {
+        self.output_dir.as_deref().unwrap_or("./output")
+    }
+
+    /// API 응답 원문 보관 경로
+    pub fn raw_archive_dir(&self) -> PathBuf {
+        Path::new(self.output_dir()).join(".knbackup").join("raw")
+    }
+
+    /// 저장할 결과물
+    pub fn sinks(&self) -> Vec<BackupSink> {
+        let mut sinks = BackupSink::all();
+        if self.is_report_card() {
+            sinks.push(BackupSink::ReportCard);
+        }
+        sinks
+    }
 }
 
 impl Default for DownloadArgs {
@@ -106,51 +243,213 @@ impl Default for DownloadArgs {
     }
 }
 
-pub struct DownloadSource {
-    pub source_type: String,
-    pub source_id: u64,
-    pub report_date: DateTime<Utc>,
-    pub report_content: Option<String>,
-    pub author_name: String,
-    pub center_name: Option<String>,
-    pub class_id: u64,
-    pub class_name: String,
-    pub child_id: u64,
-    pub child_name: String,
-    pub attached_images: Vec<ResourceImageResponse>,
-}
-
 pub struct DownloadCommand {
-    args: DownloadArgs,
-    config: KnBackupConfig,
-    kidsnote_sdk: KidsnoteSdk,
+    pub(crate) args: DownloadArgs,
+    pub(crate) kidsnote_sdk: KidsnoteSdk,
+    pub(crate) summary: RunSummary,
+    pub(crate) state: BackupState,
+    pub(crate) event_handler: Option<Box<dyn BackupEventHandler>>,
+    /// 이번 실행에서 받은 알림장 id
+    pub(crate) seen_reports: HashSet<u64>,
+    /// 알림장 이미지 테마 ([render])
+    pub(crate) theme: RenderTheme,
 }
 
 impl DownloadCommand {
-    pub async fn run(args: &DownloadArgs) {
+    pub async fn run(
+        args: &DownloadArgs,
+        event_handler: Option<Box<dyn BackupEventHandler>>,
+    ) -> RunSummary {
+        if args.all_profiles {
+            return Self::run_all_profiles(args, event_handler).await;
+        }
+        let mut inst = Self::new(args, "download", event_handler);
+        if let Some(_lock) = inst.lock_output() {
+            inst.next().await;
+        }
+        inst.summary
+    }
+
+    /// config 의 모든 계정으로 차례대로 받는다.
+    /// - 계정별 출력 경로에 받는다. 같은 출력 경로에 받은 앞 계정의 알림장은 건너뛴다.
+    async fn run_all_profiles(
+        args: &DownloadArgs,
+        mut event_handler: Option<Box<dyn BackupEventHandler>>,
+    ) -> RunSummary {
+        let mut summary = RunSummary::new("download");
+        let config = match KnBackupConfig::from_file(&args.config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.error(err.to_string());
+                return summary;
+            }
+        };
+        let profile_names = config.profile_names();
+        if profile_names.is_empty() {
+            log::error!(target:"download", "no profile in config. path={}", args.config_path);
+            summary.error(String::from("no profile in config."));
+            return summary;
+        }
+
+        // 출력 경로별로 받은 알림장
+        let mut seen_reports: HashMap<String, HashSet<u64>> = HashMap::new();
+        for (index, profile) in profile_names.into_iter().enumerate() {
+            log::info!(target:"download", "[profile][{}] download start.", profile.as_deref().unwrap_or("default"));
+            let mut profile_args = args.clone();
+            profile_args.all_profiles = false;
+            profile_args.profile = profile;
+            profile_args.user_id = None;
+            profile_args.user_pass = None;
+            profile_args.refresh_token = None;
+
+            let mut inst = Self::new(&profile_args, "download", event_handler.take());
+            let output_dir = inst.args.output_dir().to_string();
+            inst.seen_reports = seen_reports.remove(&output_dir).unwrap_or_default();
+            if let Some(_lock) = inst.lock_output() {
+                inst.next().await;
+            }
+            seen_reports.insert(output_dir, std::mem::take(&mut inst.seen_reports));
+            event_handler = inst.event_handler.take();
+            if index == 0 {
+                summary = inst.summary;
+            } else {
+                summary.merge(inst.summary);
+            }
+        }
+        summary
+    }
+
+    /// config 와 상태 파일을 읽고 sdk 를 준비한다.
+    pub(crate) fn new(
+        args: &DownloadArgs,
+        command: &str,
+        event_handler: Option<Box<dyn BackupEventHandler>>,
+    ) -> Self {
         let mut args = args.clone();
         let config_path = args.config_path.clone();
-        let config = KnBackupConfig::from_file(&config_path);
-        if let Some(profile) = &config.default {
-            args.update_profile(profile);
+        let mut summary = RunSummary::new(command);
+        // 읽을 수 없는 config 는 로그인 정보를 저장하지 않도록 token store 로 쓰지 않는다.
+        let (mut config, config_ok) = match KnBackupConfig::from_file(&config_path) {
+            Ok(config) => (config, true),
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.auth_fail(err.to_string());
+                (KnBackupConfig::default(), false)
+            }
+        };
+        match config.login_profile(args.profile.as_deref()) {
+            Ok(Some(profile)) => args.update_profile(&profile),
+            Ok(None) => {}
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                if args.refresh_token.is_none() && args.user_pass.is_none() {
+                    summary.auth_fail(err.to_string());
+                }
+            }
         }
+        args.apply_config(&config.download_config(args.profile.as_deref()));
+        let theme = config.render_theme().unwrap_or_else(|err| {
+            log::error!(target:"config", "{}", err);
+            summary.error(format!("render theme fail. use default. {}", err));
+            RenderTheme::default()
+        });
 
         let kidsnote_options = KidsnoteOptions::new(args.client_id.clone());
-        let kidsnote_sdk = KidsnoteSdk::new(kidsnote_options);
+        let mut kidsnote_sdk = KidsnoteSdk::new(kidsnote_options);
         if let Some(refresh_token) = args.refresh_token.clone() {
             kidsnote_sdk.set_refresh_token(refresh_token, args.user_id.clone());
         }
+        if config_ok {
+            kidsnote_sdk.set_token_store(config.into_token_store(
+                args.profile.clone(),
+                args.config_path.clone(),
+                TokenStorage::Saved,
+            ));
+        }
+        if args.is_archive_raw() {
+            kidsnote_sdk.set_response_archive(Some(ResponseArchive::new(args.raw_archive_dir())));
+        }
 
-        let mut inst = Self {
+        let state = BackupState::from_dir(args.output_dir());
+        Self {
             args,
-            config,
             kidsnote_sdk,
-        };
-        inst.next().await;
+            summary,
+            state,
+            event_handler,
+            seen_reports: HashSet::new(),
+            theme,
+        }
     }
 
-    ///
+    /// 다른 실행과 겹치지 않도록 출력 폴더를 잠그고 상태 파일을 다시 읽는다.
+    pub(crate) fn lock_output(&mut self) -> Option<OutputLock> {
+        match OutputLock::acquire(self.args.output_dir(), &self.summary.command) {
+            Ok(lock) => {
+                self.state = BackupState::from_dir(self.args.output_dir());
+                Some(lock)
+            }
+            Err(err) => {
+                log::error!(target:"lock", "{}", err);
+                self.summary.error(err);
+                None
+            }
+        }
+    }
+
+    /// 로그인 후 자녀별 알림장 다운로드
     async fn next(&mut self) {
+        if let Some(me) = self.step_login().await {
+            let job = BackupJob::builder()
+                .output_dir(self.args.output_dir())
+                .date_start(self.args.date_start.clone())
+                .date_end(self.args.date_end.clone())
+                .dry_run(self.args.is_test())
+                .sinks(self.args.sinks())
+                .skip_reports(self.seen_reports.clone())
+                .fallbacks(self.state.fallback_sizes())
+                .theme(self.theme.clone())
+                .children(me.children)
+                .build();
+            self.run_job(job).await;
+        }
+    }
+
+    /// 백업 작업 실행. 이벤트로 요약, 상태 파일, 진행 표시를 갱신한다.
+    pub(crate) async fn run_job(&mut self, mut job: BackupJob) {
+        let output_dir = self.args.output_dir().to_string();
+        let summary = &mut self.summary;
+        let state = &mut self.state;
+        let event_handler = &mut self.event_handler;
+        let seen_reports = &mut self.seen_reports;
+        let mut handler = |event: &BackupEvent| {
+            if let BackupEvent::ReportDiscovered { report, .. } = event {
+                seen_reports.insert(report.report_id);
+            }
+            summary.on_event(event);
+            state.on_event(event);
+            if let Some(event_handler) = event_handler.as_mut() {
+                event_handler.on_event(event);
+            }
+            // 중간에 종료되어도 실패 목록이 남도록 페이지마다 저장
+            if let BackupEvent::ReportPageFetched { .. }
+            | BackupEvent::ChildFinished { .. }
+            | BackupEvent::Finished = event
+            {
+                state.save(&output_dir);
+            }
+        };
+
+        if let Err(err) = job.run(&mut self.kidsnote_sdk, &mut handler).await {
+            log::error!(target:"backup", "backup job fail. {}", err);
+            self.summary
+                .request_error(&err, format!("backup job fail. {}", err));
+        }
+    }
+
+    /// 로그인 후 내정보 조회
+    pub(crate) async fn step_login(&mut self) -> Option<MeInfoResponse> {
         log::info!(target:"login","kidsnote user refresh_token checking..");
         let auth_result = if let Some(refresh_token) = self.args.refresh_token.clone() {
             log::info!(target:"login","kidsnote user refresh_token login mode start.");
@@ -166,6 +465,7 @@ impl DownloadCommand {
                 }
                 Err(err) => {
                     log::error!(target:"login","kidsnote user refresh_token fail. {}", err);
+                    self.login_fail(&err, format!("refresh_token login fail. {}", err));
                     None
                 }
             }
@@ -185,36 +485,40 @@ impl DownloadCommand {
                 }
                 Err(err) => {
                     log::error!(target:"login","kidsnote user password login fail. {}", err);
+                    self.login_fail(&err, format!("password login fail. {}", err));
                     None
                 }
             }
         } else {
+            if !self.summary.auth_failed {
+                log::error!(target:"login","Error. Invalid args");
+                self.summary
+                    .auth_fail(String::from("invalid args. no login info"));
+            }
             None
         };
 
-        if let Some(auth_result) = auth_result {
-            if let Ok(me) = self.step_myinfo().await {
-                self.config
-                    .set_default(me.user.username, auth_result.refresh_token.clone());
-                self.config.save(self.args.config_path.clone());
-
-                for child in me.children {
-                    log::info!(target:"myinfo", "[child][{}] center look up.", child.name);
-                    let mut center_map = HashMap::new();
-                    for enroll in child.enrollment {
-                        center_map.entry(enroll.center_id)
-                            .or_insert(enroll.center_name);
-                    }
-                    
-                    if let Err(err) = self.step_child_report_download(child.id, child.name.clone(), center_map)
-                        .await
-                    {
-                        log::error!(target:"myinfo","step_child_report_download error. {}", err);
-                    }
+        // 새 refresh token 은 token store 에서 바로 저장된다.
+        if auth_result.is_some() {
+            match self.step_myinfo().await {
+                Ok(me) => Some(me),
+                Err(err) => {
+                    self.summary
+                        .request_error(&err, format!("myinfo look up fail. {}", err));
+                    None
                 }
             }
         } else {
-            log::error!(target:"login","Error. Invalid args");
+            None
+        }
+    }
+
+    /// 로그인 정보가 틀린 경우만 인증 실패. 서버, 네트워크 오류는 일반 오류로 남긴다.
+    fn login_fail(&mut self, err: &AuthError, message: String) {
+        if err.is_invalid_credentials() {
+            self.summary.auth_fail(message);
+        } else {
+            self.summary.request_error(err, message);
         }
     }
 
@@ -243,241 +547,4 @@ impl DownloadCommand {
             }
         }
     }
-
-    /// 알림장
-    async fn step_child_report_download(
-        &mut self,
-        child_id: u64,
-        child_name: String,
-        center_map: HashMap<u64, String>,
-    ) -> Result<i32, AuthError> {
-        log::info!(target:"report","Child:[{}]:Start", child_id);
-
-        let mut result = 0;
-
-        // cls로는 필터링 되는데 center로는 필터가 안된다.
-
-        // 날짜 필터링
-        let mut report_options = GetReportsParam::new();
-        if self.args.date_start.is_some() && self.args.date_end.is_some() {
-            report_options.date_start = self.args.date_start.clone();
-            report_options.date_end = self.args.date_end.clone();
-            report_options.tz = Some("Asia/Seoul".to_string());
-        } else if self.args.date_start.is_some() {
-            report_options.date_start = self.args.date_start.clone();
-            report_options.date_end = self.args.date_start.clone();
-            report_options.tz = Some("Asia/Seoul".to_string());
-        } else if self.args.date_end.is_some() {
-            report_options.date_start = self.args.date_end.clone();
-            report_options.date_end = self.args.date_end.clone();
-            report_options.tz = Some("Asia/Seoul".to_string());
-        }
-
-        let mut loop_count = 0;
-        loop {
-            loop_count += 1;
-
-            log::info!(target: "report", "[Child][{}][report] look up. page={:?}, ds={:?}, de={:?}", child_name, report_options.page, report_options.date_start, report_options.date_end);
-            match self
-                .kidsnote_sdk
-                .child()
-                .get_reports(child_id, Some(report_options.clone()))
-                .await
-            {
-                Ok(report_result) => {
-                    report_options.page = report_result.next.clone();
-
-                    if !report_result.results.is_empty() {
-                        let mut download_sources = Vec::new();
-                        for report in report_result.results {
-                            let center_name = report.center
-                                .and_then(|f| center_map.get(&f).cloned())
-                                .or(Some(String::from("")));
-
-                            download_sources.push(DownloadSource {
-                                source_type: String::from("알림장"),
-                                source_id: report.id,
-                                report_date: report.created,
-                                report_content: report.content,
-                                author_name: report.author_name,
-                                center_name: center_name,
-                                class_id: report.cls,
-                                class_name: report.class_name.clone(),
-                                child_id,
-                                child_name: child_name.clone(),
-                                attached_images: report.attached_images,
-                            });
-                            result += 1;
-                        }
-                        self.step_child_report_sourece_download(download_sources)
-                            .await;
-                    } else {
-                        report_options.page = None;
-                    }
-                }
-                Err(err) => {
-                    report_options.page = None;
-                    log::error!(target: "report", "[Child][{}][report] look up error. {}", child_name, err);
-                    return Err(err);
-                }
-            }
-
-            if report_options.page.is_none() {
-                break;
-            }
-
-            tokio::time::sleep(Duration::from_secs(1)).await;
-
-            if loop_count > 10000 {
-                log::error!("The loop count is excessive. loop={}", loop_count);
-                break;
-            } 
-        }
-
-        log::info!("[child][{}][report] End.", child_id);
-
-        Ok(result)
-    }
-
-    async fn step_child_report_sourece_download(&mut self, sources: Vec<DownloadSource>) {
-        for source in sources {
-            // 알림장 텍스트 변환해서 저장
-            let title = format!(
-                "제목 : {} {}",
-                Utc.from_utc_datetime(&source.report_date.naive_utc()).format("%Y년 %-m월 %-e일"),
-                source.source_type
-            );
-
-            let mut output_base_path = PathBuf::from(&self.args.output_dir);
-            output_base_path.push(format!(
-                "키즈노트 {}",
-                source.child_name
-            ));
-            output_base_path.push("알림장");
-            output_base_path.push(source.report_date.format("%Y-%m").to_string());
-
-            let mut text_file = output_base_path.clone();
-            text_file.push(format!(
-                "{}_{}_{}_{}.txt",
-                source.report_date.format("%Y%m%d"),
-                source.child_name,
-                source.source_type,
-                source.source_id
-            ));
-
-            let mut image_file = output_base_path.clone();
-            image_file.push(format!(
-                "{}_{}_{}_{}.jpg",
-                source.report_date.format("%Y%m%d"),
-                source.child_name,
-                source.source_type,
-                source.source_id
-            ));
-
-            if let Some(contents) = source.report_content {
-                if !contents.trim().is_empty() {
-                    let new_contents = contents.replace("  ", " ");
-                    let new_contents: Vec<&str> = new_contents.lines()
-                        .map(|s| s.trim())
-                        .collect();
-                    if !new_contents.is_empty() {
-                        if !self.args.test {
-                            if let Some(output_file) = text_file.to_str() {
-                                match ImageTool::text_to_txt_file(
-                                    title.as_str(),
-                                    &source.center_name,
-                                    &source.author_name,
-                                    &new_contents,
-                                    output_file,
-                                    source.report_date,
-                                ) {
-                                    Ok(_) => {
-                                        log::info!(target: "report", "[Child][{}][report][{}][Content] text save.", source.child_name, source.source_id);
-                                    }
-                                    Err(err) => {
-                                        log::error!(target: "report", "[Child][{}][report][{}][Content] text save error. {}", source.child_name, source.source_id, err);
-                                    }
-                                }
-                            }
-                            if let Some(output_file) = image_file.to_str() {
-                                match ImageTool::text_to_image_file(
-                                    title.as_str(),
-                                    &source.center_name,
-                                    &source.author_name,
-                                    &new_contents,
-                                    output_file,
-                                    source.report_date,
-                                ) {
-                                    Ok(_) => {
-                                        log::info!(target: "report", "[Child][{}][report][{}][Content] Convert text to image and save.", source.child_name, source.source_id);
-                                    }
-                                    Err(err) => {
-                                        log::error!(target: "report", "[Child][{}][report][{}][Content] Convert text to image and save error. {}", source.child_name, source.source_id, err);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-            
-            // 이미지 다운로드 받기
-            let file_time = FileTime::from_unix_time(source.report_date.timestamp(), 0);
-            for image in source.attached_images {
-                let original_file = Path::new(&image.original_file_name);
-                let extension = original_file
-                    .extension()
-                    .and_then(|e| e.to_str())
-                    .unwrap_or("png");
-                let mut output_file = output_base_path.clone();
-                output_file.push(format!(
-                    "{}_{}_{}_{}_{}.{}",
-                    source.report_date.format("%Y%m%d"),
-                    source.child_name,
-                    source.source_type,
-                    source.source_id,
-                    image.id,
-                    extension
-                ));
-                //let path = format!("{}/{}/{}/report_{}_{}_{}_{}.{}", self.args.output_dir, source.child_name, source.report_date.format("%Y-%m-%d"), source.center_name, source.report_id, source.report_date.format("%Y%m%d"), image.id, extension);
-                if let Some(output_file) = output_file.to_str() {
-                    if !self.args.test {
-                        match self
-                            .kidsnote_sdk
-                            .resource()
-                            .download_image(
-                                &image.original,
-                                image.file_size,
-                                file_time,
-                                output_file,
-                            )
-                            .await
-                        {
-                            Ok(download_result) => {
-                                if download_result {
-                                    log::info!(target: "report", "[Child][{}][report][{}][Image][{}] download.", source.child_name, source.source_id, image.id);
-                                    log::debug!(target: "report", "File created. path={}", output_file);
-                                } else {
-                                    log::info!(target: "report", "[Child][{}][report][{}][Image][{:?}] download skip.", source.child_name, source.source_id, image.id);
-                                    log::debug!(target: "report", "File skip. path={}", output_file);
-                                }
-                            }
-                            Err(err) => {
-                                log::error!(target: "report", "[Child][{}][report][{}][Image][{}] download error. {}", source.child_name, source.source_id, image.id, err);
-                            }
-                        }
-                    } else {
-                        log::info!(target: "report", "[Test][Child][{}][report][{}][Image][{}] download.", source.child_name, source.source_id, image.id);
-                        log::error!(target: "report", "Download error. path={}", output_file);
-                    }
-                    tokio::time::sleep(Duration::from_millis(1)).await;
-                }
-            }
-
-            // (미구현) 비디오 다운로드 받기
-            // (미구현) 첨부파일 다운로드 받기
-            // (미구현) 알림장 텍스트 다운로드 받기
-            // (미구현) 댓글 다운로드 받기
-        }
-    }
 }
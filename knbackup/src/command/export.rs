@@ -0,0 +1,159 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use kidsnote_sdk::{
+    archive::ResponseArchive,
+    backup::datatypes::{BackupLayout, BackupSource},
+    export::HtmlExport,
+};
+
+use crate::{
+    command::download::DownloadArgs, kidsnote::KnBackupConfig, lock::OutputLock,
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub format: ExportFormat,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ExportFormat {
+    /// Build a static website per child that opens offline (e.g. from a USB stick)
+    Html(HtmlExportArgs),
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct HtmlExportArgs {
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml"
+    )]
+    pub config_path: String,
+
+    /// Output path [default: ./output]
+    #[arg(
+        short = 'o',
+        long = "output-path",
+        env = "KNB_OUTPUT_PATH",
+        value_name = "Output Path"
+    )]
+    pub output_dir: Option<String>,
+
+    /// Website path [default: {output path}/html]
+    #[arg(long = "export-path", value_name = "Export Path")]
+    pub export_path: Option<String>,
+
+    /// Thumbnail size in pixels
+    #[arg(long = "thumbnail-size", value_name = "Pixels", default_value_t = 240)]
+    pub thumbnail_size: u32,
+}
+
+impl From<&HtmlExportArgs> for DownloadArgs {
+    fn from(args: &HtmlExportArgs) -> Self {
+        let mut download_args = DownloadArgs::new();
+        download_args.profile = args.profile.clone();
+        download_args.config_path = args.config_path.clone();
+        download_args.output_dir = args.output_dir.clone();
+        download_args
+    }
+}
+
+pub struct ExportCommand {}
+
+impl ExportCommand {
+    pub async fn run(args: &ExportArgs) -> RunSummary {
+        match &args.format {
+            ExportFormat::Html(args) => Self::html(args),
+        }
+    }
+
+    /// 받아둔 알림장(.json, 응답 보관 파일)과 사진으로 자녀별 정적 웹 페이지를 만든다.
+    /// - 서버에 요청하지 않는다.
+    fn html(args: &HtmlExportArgs) -> RunSummary {
+        let mut summary = RunSummary::new("export");
+        let config = match KnBackupConfig::from_file(&args.config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.error(err.to_string());
+                return summary;
+            }
+        };
+        let mut download_args = DownloadArgs::from(args);
+        download_args.apply_config(&config.download_config(args.profile.as_deref()));
+
+        let output_dir = download_args.output_dir().to_string();
+        let export_dir = Self::export_dir(&output_dir, args.export_path.as_deref());
+        let _lock = match OutputLock::acquire(&output_dir, "export") {
+            Ok(lock) => lock,
+            Err(err) => {
+                log::error!(target:"lock", "{}", err);
+                summary.error(err);
+                return summary;
+            }
+        };
+
+        let layout = BackupLayout::new(&output_dir);
+        let raw_archive_dir = download_args.raw_archive_dir();
+        let archive =
+            Some(ResponseArchive::new(&raw_archive_dir)).filter(|_| raw_archive_dir.is_dir());
+        let local = layout.local_sources(archive.as_ref());
+        for error in local.errors {
+            log::error!(target:"export", "{}", error);
+            summary.error(error);
+        }
+        if local.sources.is_empty() {
+            log::warn!(target:"export", "no saved report. path={}", output_dir);
+            return summary;
+        }
+        log::info!(target:"export", "reports={}, path={}", local.sources.len(), export_dir.display());
+        let reports = Self::export_html(
+            layout,
+            &export_dir,
+            args.thumbnail_size,
+            &local.sources,
+            &mut summary,
+        );
+        summary.reports_seen += reports;
+        summary
+    }
+
+    /// 웹 페이지 경로. 지정하지 않으면 {output}/html
+    pub fn export_dir(output_dir: &str, export_path: Option<&str>) -> PathBuf {
+        match export_path {
+            Some(export_path) => PathBuf::from(export_path),
+            None => PathBuf::from(output_dir).join("html"),
+        }
+    }
+
+    /// 웹 페이지를 만들고 오류, 만든 페이지 수를 summary 에 더한다. 만든 알림장 페이지 수를 돌려준다.
+    pub fn export_html(
+        layout: BackupLayout,
+        export_dir: &Path,
+        thumbnail_size: u32,
+        sources: &[BackupSource],
+        summary: &mut RunSummary,
+    ) -> u64 {
+        let result = HtmlExport::new(layout, export_dir)
+            .thumbnail_size(thumbnail_size)
+            .export(sources);
+        for error in result.errors {
+            log::error!(target:"export", "{}", error);
+            summary.error(error);
+        }
+        summary.files_rendered += result.pages;
+        if result.photos_missing > 0 {
+            log::warn!(target:"export", "photos not downloaded={}", result.photos_missing);
+        }
+        log::info!(target:"export", "children={}, reports={}, photos={}, path={}", result.children, result.reports, result.photos, export_dir.join("index.html").display());
+        result.reports
+    }
+}
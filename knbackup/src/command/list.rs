@@ -0,0 +1,272 @@
+use std::{collections::BTreeMap, fs};
+
+use clap::{Parser, ValueEnum};
+use kidsnote_sdk::{
+    backup::datatypes::BackupFilter,
+    child::{datatypes::ChildReportResponse, KidsnoteChildSdk},
+    user::datatypes::MeInfoChildrenResponse,
+};
+use serde::Serialize;
+
+use crate::{
+    command::download::{DownloadArgs, DownloadCommand},
+    summary::RunSummary,
+};
+
+/// 출력 형식
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFormat {
+    Table,
+    Json,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ListArgs {
+    /// Client ID
+    #[arg(
+        short = 'c',
+        long = "client_id",
+        env = "KNB_CLIENT_ID",
+        value_name = "Client id"
+    )]
+    pub client_id: Option<String>,
+
+    /// UserID of the Account to greet
+    #[arg(
+        short = 'u',
+        long = "user",
+        env = "KNB_USER_ID",
+        value_name = "User ID"
+    )]
+    pub user_id: Option<String>,
+
+    /// Password of the Account to greet
+    #[arg(
+        short = 'p',
+        long = "pass",
+        env = "KNB_USER_PASS",
+        value_name = "User Password"
+    )]
+    pub user_pass: Option<String>,
+
+    /// RefreshToken of the Account to greet
+    #[arg(short = 'r', long = "refresh-token")]
+    pub refresh_token: Option<String>,
+
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml"
+    )]
+    pub config_path: String,
+
+    /// Start date
+    #[arg(long, alias = "ds", value_name = "Start Date")]
+    pub date_start: Option<String>,
+
+    /// End date
+    #[arg(long, alias = "de", value_name = "End Date")]
+    pub date_end: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value = "table")]
+    pub format: ListFormat,
+
+    /// Write the result to a file instead of stdout
+    #[arg(long = "output-file", value_name = "Output File Path")]
+    pub output_file: Option<String>,
+
+    /// Reports per request (larger is fewer requests)
+    #[arg(long = "page-size", default_value = "100", value_name = "Page Size")]
+    pub page_size: i32,
+}
+
+impl From<&ListArgs> for DownloadArgs {
+    fn from(args: &ListArgs) -> Self {
+        let mut download_args = DownloadArgs::new();
+        download_args.client_id = args.client_id.clone();
+        download_args.user_id = args.user_id.clone();
+        download_args.user_pass = args.user_pass.clone();
+        download_args.refresh_token = args.refresh_token.clone();
+        download_args.profile = args.profile.clone();
+        download_args.config_path = args.config_path.clone();
+        download_args
+    }
+}
+
+/// 원/반 등록 정보
+#[derive(Debug, Serialize)]
+pub struct ListEnrollment {
+    pub center_id: u64,
+    pub center_name: String,
+    pub class_id: u64,
+    pub class_name: String,
+    pub is_approved: bool,
+    pub removed_child: bool,
+    pub created: String,
+    pub modified: String,
+}
+
+/// 월별 개수
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct ListMonthCount {
+    pub month: String,
+    pub reports: u64,
+    pub images: u64,
+    pub comments: u64,
+}
+
+impl ListMonthCount {
+    fn add(&mut self, other: &ListMonthCount) {
+        self.reports += other.reports;
+        self.images += other.images;
+        self.comments += other.comments;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListChild {
+    pub id: u64,
+    pub name: String,
+    pub date_birth: String,
+    pub enrollments: Vec<ListEnrollment>,
+    pub months: Vec<ListMonthCount>,
+    pub total: ListMonthCount,
+}
+
+impl ListChild {
+    fn new(child: &MeInfoChildrenResponse) -> Self {
+        Self {
+            id: child.id,
+            name: child.name.clone(),
+            date_birth: child.date_birth.clone(),
+            enrollments: child
+                .enrollment
+                .iter()
+                .map(|f| ListEnrollment {
+                    center_id: f.center_id,
+                    center_name: f.center_name.clone(),
+                    class_id: f.belong_to_class,
+                    class_name: f.class_name.clone(),
+                    is_approved: f.is_approved,
+                    removed_child: f.removed_child,
+                    created: f.created.clone(),
+                    modified: f.modified.clone(),
+                })
+                .collect(),
+            months: Vec::new(),
+            total: ListMonthCount {
+                month: String::from("total"),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+pub struct ListCommand {}
+
+impl ListCommand {
+    /// 자녀, 원/반 등록 정보와 월별 알림장, 이미지, 댓글 수 출력
+    pub async fn run(args: &ListArgs) -> RunSummary {
+        let download_args = DownloadArgs::from(args);
+        let mut download = DownloadCommand::new(&download_args, "list", None);
+        let Some(me) = download.step_login().await else {
+            return download.summary;
+        };
+
+        let filter = BackupFilter {
+            date_start: args.date_start.clone(),
+            date_end: args.date_end.clone(),
+        };
+        let mut param = filter.to_param();
+        param.page_size = Some(args.page_size.max(1));
+
+        let mut children = Vec::new();
+        for child in &me.children {
+            let mut item = ListChild::new(child);
+            let mut months: BTreeMap<String, ListMonthCount> = BTreeMap::new();
+            let result = download
+                .kidsnote_sdk
+                .child()
+                .get_reports_pages(child.id, param.clone(), |page: &ChildReportResponse| {
+                    for report in &page.results {
+                        let date = KidsnoteChildSdk::report_local_date(report.created);
+                        let month = months.entry(date[..7].to_string()).or_default();
+                        month.reports += 1;
+                        month.images += report.attached_images.len() as u64;
+                        month.comments += report.num_comments.max(0) as u64;
+                    }
+                })
+                .await;
+            if let Err(err) = result {
+                log::error!(target:"list", "[{}] report look up fail. {}", child.name, err);
+                download.summary.reports_failed += 1;
+                download
+                    .summary
+                    .error(format!("[{}] report look up fail. {}", child.name, err));
+            }
+
+            for (month, mut count) in months {
+                count.month = month;
+                item.total.add(&count);
+                item.months.push(count);
+            }
+            download.summary.reports_seen += item.total.reports;
+            children.push(item);
+        }
+
+        let output = match args.format {
+            ListFormat::Table => Self::to_table(&children),
+            ListFormat::Json => serde_json::to_string_pretty(&children).unwrap(),
+        };
+        match &args.output_file {
+            Some(output_file) => {
+                if let Err(err) = fs::write(output_file, output) {
+                    log::error!(target:"list", "list file save fail. {}", err);
+                    download
+                        .summary
+                        .error(format!("list file save fail. {}", err));
+                }
+            }
+            None => println!("{}", output),
+        }
+        download.summary
+    }
+
+    fn to_table(children: &[ListChild]) -> String {
+        let mut lines = Vec::new();
+        for child in children {
+            lines.push(format!(
+                "{} (id={}, birth={})",
+                child.name, child.id, child.date_birth
+            ));
+            for enroll in &child.enrollments {
+                lines.push(format!(
+                    "  {} / {} approved={} removed={} created={}",
+                    enroll.center_name,
+                    enroll.class_name,
+                    enroll.is_approved,
+                    enroll.removed_child,
+                    enroll.created
+                ));
+            }
+            lines.push(format!(
+                "  {:<8} {:>8} {:>8} {:>8}",
+                "month", "reports", "images", "comments"
+            ));
+            for count in child.months.iter().chain([&child.total]) {
+                lines.push(format!(
+                    "  {:<8} {:>8} {:>8} {:>8}",
+                    count.month, count.reports, count.images, count.comments
+                ));
+            }
+            lines.push(String::new());
+        }
+        lines.join("\n")
+    }
+}
@@ -1,10 +1,15 @@
+use std::io::IsTerminal;
+
 use clap::Parser;
 use kidsnote_sdk::{
     auth::error_types::AuthError, options::KidsnoteOptions, user::datatypes::MeInfoResponse,
     KidsnoteSdk,
 };
 
-use crate::kidsnote::{KidsnoteConfigProfile, KnBackupConfig};
+use crate::{
+    kidsnote::{KidsnoteConfigProfile, KnBackupConfig, TokenStorage},
+    summary::RunSummary,
+};
 
 #[derive(Parser, Debug, Clone)]
 pub struct LoginArgs {
@@ -35,10 +40,22 @@ pub struct LoginArgs {
     )]
     pub user_pass: Option<String>,
 
+    /// Read the password from stdin (first line)
+    #[arg(long = "password-stdin")]
+    pub password_stdin: bool,
+
     /// RefreshToken of the Account to greet
     #[arg(short = 'r', long = "refresh-token")]
     pub refresh_token: Option<String>,
 
+    /// Save the refresh token without encryption (no passphrase)
+    #[arg(long = "plaintext-token")]
+    pub plaintext_token: bool,
+
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
     // Sets a custom config file
     #[arg(
         long = "config",
@@ -49,49 +66,138 @@ pub struct LoginArgs {
 }
 
 impl LoginArgs {
-    ///
+    /// config 의 로그인 정보로 비어있는 값을 채운다.
+    /// - 비밀번호를 입력한 경우 저장된 refresh token 대신 비밀번호로 로그인한다.
     fn update_profile(&mut self, profile: &KidsnoteConfigProfile) {
-        if self.refresh_token.is_none() && profile.refresh_token.is_some() {
+        if self.refresh_token.is_none()
+            && self.user_pass.is_none()
+            && profile.refresh_token.is_some()
+        {
             self.refresh_token = profile.refresh_token.clone();
         }
         if self.user_id.is_none() && profile.user_id.is_some() {
             self.user_id = profile.user_id.clone();
         }
     }
+
+    /// --password-stdin : 표준 입력의 첫 줄을 비밀번호로 사용
+    fn read_password_stdin(&mut self) -> Result<(), String> {
+        let mut line = String::new();
+        std::io::stdin()
+            .read_line(&mut line)
+            .map_err(|err| format!("password read fail. {}", err))?;
+        let password = line.trim_end_matches(['\r', '\n']);
+        if password.is_empty() {
+            return Err(String::from("password is empty."));
+        }
+        self.user_pass = Some(password.to_string());
+        Ok(())
+    }
+
+    /// 로그인 정보가 없고 터미널이면 아이디, 비밀번호를 입력 받는다.
+    /// - 비밀번호는 화면에 표시하지 않는다.
+    fn prompt_login(&mut self) -> Result<(), String> {
+        if self.refresh_token.is_some()
+            || self.user_pass.is_some()
+            || !std::io::stdin().is_terminal()
+        {
+            return Ok(());
+        }
+        if self.user_id.is_none() {
+            eprint!("User ID: ");
+            let mut line = String::new();
+            std::io::stdin()
+                .read_line(&mut line)
+                .map_err(|err| format!("user id read fail. {}", err))?;
+            let user_id = line.trim();
+            if user_id.is_empty() {
+                return Err(String::from("user id is empty."));
+            }
+            self.user_id = Some(user_id.to_string());
+        }
+        let password = rpassword::prompt_password("Password: ")
+            .map_err(|err| format!("password read fail. {}", err))?;
+        if password.is_empty() {
+            return Err(String::from("password is empty."));
+        }
+        self.user_pass = Some(password);
+        Ok(())
+    }
 }
 
 pub struct LoginCommand {
     args: LoginArgs,
-    config: KnBackupConfig,
     kidsnote_sdk: KidsnoteSdk,
+    summary: RunSummary,
 }
 
 impl LoginCommand {
     /// init and run
-    pub async fn run(args: &LoginArgs) {
+    pub async fn run(args: &LoginArgs) -> RunSummary {
         let mut args = args.clone();
         //let config_path = args.config_path.clone().unwrap_or(String::from("~/.knbackup/config.toml"));
         let config_path = args.config_path.clone();
-        let config = KnBackupConfig::from_file(&config_path);
-        if let Some(profile) = &config.default {
-            args.update_profile(profile);
+        let mut summary = RunSummary::new("login");
+        let mut config = match KnBackupConfig::from_file(&config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.auth_fail(err.to_string());
+                return summary;
+            }
+        };
+        if args.password_stdin {
+            if let Err(err) = args.read_password_stdin() {
+                log::error!(target:"login", "{}", err);
+                summary.auth_fail(err);
+                return summary;
+            }
+        }
+        match config.login_profile(args.profile.as_deref()) {
+            Ok(Some(profile)) => args.update_profile(&profile),
+            Ok(None) => {}
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                if args.refresh_token.is_none() && args.user_pass.is_none() {
+                    summary.auth_fail(err.to_string());
+                }
+            }
+        }
+        if args.client_id.is_none() {
+            args.client_id = config.download_config(args.profile.as_deref()).client_id;
+        }
+
+        if let Err(err) = args.prompt_login() {
+            log::error!(target:"login", "{}", err);
+            summary.auth_fail(err);
+            return summary;
         }
 
         let kidsnote_options = KidsnoteOptions::new(args.client_id.clone());
-        let kidsnote_sdk = KidsnoteSdk::new(kidsnote_options);
+        let mut kidsnote_sdk = KidsnoteSdk::new(kidsnote_options);
         if let Some(refresh_token) = args.refresh_token.clone() {
             kidsnote_sdk.set_refresh_token(refresh_token, args.user_id.clone());
         }
+        kidsnote_sdk.set_token_store(config.into_token_store(
+            args.profile.clone(),
+            args.config_path.clone(),
+            if args.plaintext_token {
+                TokenStorage::Plaintext
+            } else {
+                TokenStorage::Encrypted
+            },
+        ));
 
         let mut inst = Self {
-            config,
             args: args.clone(),
             kidsnote_sdk,
+            summary,
         };
         inst.next().await;
+        inst.summary
     }
 
-    ///
+    /// 로그인 후 내정보 확인. config 는 token store 에서 저장된다.
     async fn next(&mut self) {
         log::info!(target:"login","kidsnote user refresh_token checking..");
         let auth_result = if let Some(refresh_token) = self.args.refresh_token.clone() {
@@ -107,7 +213,14 @@ impl LoginCommand {
                     Some(result)
                 }
                 Err(err) => {
-                    log::error!(target:"login","kidsnote user refresh_token fail. {}", err);
+                    let message = if err.is_invalid_credentials() {
+                        format!("refresh token expired or revoked. login again with user id and password. {}", err)
+                    } else {
+                        Self::request_error_message(&err)
+                    };
+                    log::error!(target:"login","kidsnote user refresh_token fail. {}", message);
+                    self.summary
+                        .auth_fail(format!("refresh_token login fail. {}", message));
                     None
                 }
             }
@@ -126,7 +239,14 @@ impl LoginCommand {
                     Some(result)
                 }
                 Err(err) => {
-                    log::error!(target:"login","kidsnote user password login fail. {}", err);
+                    let message = if err.is_invalid_credentials() {
+                        format!("wrong user id or password. {}", err)
+                    } else {
+                        Self::request_error_message(&err)
+                    };
+                    log::error!(target:"login","kidsnote user password login fail. {}", message);
+                    self.summary
+                        .auth_fail(format!("password login fail. {}", message));
                     None
                 }
             }
@@ -134,16 +254,27 @@ impl LoginCommand {
             None
         };
 
-        if let Some(auth_result) = auth_result {
-            if let Ok(me) = self.step_myinfo().await {
-                log::info!("[login] End.");
-
-                self.config
-                    .set_default(me.user.username, auth_result.refresh_token.clone());
-                self.config.save(self.args.config_path.clone());
+        if auth_result.is_some() {
+            match self.step_myinfo().await {
+                Ok(_) => {
+                    log::info!("[login] End.");
+                }
+                Err(err) => {
+                    self.summary.error(format!("myinfo look up fail. {}", err));
+                }
             }
-        } else {
+        } else if !self.summary.auth_failed {
             log::error!(target:"login","Error. Invalid args");
+            self.summary.auth_fail(String::from("invalid args. no login info"));
+        }
+    }
+
+    /// 로그인 정보 외의 오류 (서버, 네트워크)
+    fn request_error_message(err: &AuthError) -> String {
+        if err.is_server_error() {
+            format!("kidsnote server error. try again later. {}", err)
+        } else {
+            format!("login request fail. {}", err)
         }
     }
 
@@ -0,0 +1,101 @@
+use clap::Parser;
+use kidsnote_sdk::{options::KidsnoteOptions, KidsnoteSdk};
+
+use crate::{
+    kidsnote::{KnBackupConfig, TokenStorage},
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct LogoutArgs {
+    /// Client ID
+    #[arg(
+        short = 'c',
+        long = "client_id",
+        env = "KNB_CLIENT_ID",
+        value_name = "Client id"
+    )]
+    pub client_id: Option<String>,
+
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml"
+    )]
+    pub config_path: String,
+
+    /// Revoke the refresh token on the server
+    #[arg(long = "revoke")]
+    pub revoke: bool,
+}
+
+pub struct LogoutCommand {}
+
+impl LogoutCommand {
+    /// config 에서 계정 정보를 지운다.
+    pub async fn run(args: &LogoutArgs) -> RunSummary {
+        let mut summary = RunSummary::new("logout");
+        let profile_name = args.profile.as_deref().unwrap_or("default");
+        let mut config = match KnBackupConfig::from_file(&args.config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.error(err.to_string());
+                return summary;
+            }
+        };
+        if config.profile(args.profile.as_deref()).is_none() {
+            log::info!(target:"logout", "[{}] not logged in.", profile_name);
+            return summary;
+        }
+
+        let client_id = args
+            .client_id
+            .clone()
+            .or(config.download_config(args.profile.as_deref()).client_id);
+        let mut kidsnote_sdk = KidsnoteSdk::new(KidsnoteOptions::new(client_id));
+        if args.revoke {
+            match config.login_profile(args.profile.as_deref()) {
+                Ok(Some(profile)) => {
+                    if let Some(refresh_token) = profile.refresh_token {
+                        match kidsnote_sdk.auth().revoke_token(&refresh_token).await {
+                            Ok(_) => {
+                                log::info!(target:"logout", "[{}] refresh token revoked.", profile_name);
+                            }
+                            Err(err) => {
+                                log::error!(target:"logout", "[{}] refresh token revoke fail. {}", profile_name, err);
+                                summary.error(format!("refresh token revoke fail. {}", err));
+                            }
+                        }
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    log::error!(target:"logout", "[{}] refresh token revoke fail. {}", profile_name, err);
+                    summary.error(format!("refresh token revoke fail. {}", err));
+                }
+            }
+        }
+
+        kidsnote_sdk.set_token_store(config.into_token_store(
+            args.profile.clone(),
+            args.config_path.clone(),
+            TokenStorage::Saved,
+        ));
+        match kidsnote_sdk.auth().clear_session() {
+            Ok(_) => {
+                log::info!(target:"logout", "[{}] logout.", profile_name);
+            }
+            Err(err) => {
+                log::error!(target:"logout", "[{}] logout fail. {}", profile_name, err);
+                summary.error(format!("logout fail. {}", err));
+            }
+        }
+        summary
+    }
+}
@@ -1,2 +1,11 @@
+pub mod config;
 pub mod download;
+pub mod export;
+pub mod list;
 pub mod login;
+pub mod logout;
+pub mod render;
+pub mod retry;
+pub mod status;
+pub mod watch;
+pub mod whoami;
@@ -0,0 +1,237 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fs,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use clap::{builder::BoolishValueParser, Parser};
+use kidsnote_sdk::{
+    archive::ResponseArchive,
+    backup::{
+        datatypes::{BackupLayout, BackupSource},
+        event::{BackupEvent, BackupEventHandler},
+        BackupJob,
+    },
+    tool::render_theme::RenderTheme,
+};
+
+use crate::{
+    command::{download::DownloadArgs, export::ExportCommand},
+    kidsnote::KnBackupConfig,
+    lock::OutputLock,
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct RenderArgs {
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml"
+    )]
+    pub config_path: String,
+
+    /// Output path [default: ./output]
+    #[arg(
+        short = 'o',
+        long = "output-path",
+        env = "KNB_OUTPUT_PATH",
+        value_name = "Output Path"
+    )]
+    pub output_dir: Option<String>,
+
+    /// Also render a report card image with the report's photos
+    #[arg(
+        long = "report-card",
+        env = "KNB_REPORT_CARD",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new(),
+        overrides_with = "no_report_card"
+    )]
+    pub report_card: Option<bool>,
+
+    /// Turn off --report-card (overrides the environment and the config file)
+    #[arg(long = "no-report-card", overrides_with = "report_card")]
+    pub no_report_card: bool,
+
+    /// Website path to rebuild if it exists [default: {output path}/html]
+    #[arg(long = "export-path", value_name = "Export Path")]
+    pub export_path: Option<String>,
+
+    /// Thumbnail size in pixels for the rebuilt website
+    #[arg(long = "thumbnail-size", value_name = "Pixels", default_value_t = 240)]
+    pub thumbnail_size: u32,
+}
+
+impl From<&RenderArgs> for DownloadArgs {
+    fn from(args: &RenderArgs) -> Self {
+        let mut download_args = DownloadArgs::new();
+        download_args.profile = args.profile.clone();
+        download_args.config_path = args.config_path.clone();
+        download_args.output_dir = args.output_dir.clone();
+        download_args.report_card = args.report_card;
+        download_args.no_report_card = args.no_report_card;
+        download_args
+    }
+}
+
+/// 다시 만든 파일
+#[derive(Debug, Default)]
+struct RenderChanges {
+    created: Vec<String>,
+    changed: Vec<String>,
+    unchanged: u64,
+    /// 쪽 수가 줄어서 지운 카드
+    removed: Vec<PathBuf>,
+}
+
+pub struct RenderCommand {}
+
+impl RenderCommand {
+    /// 받아둔 알림장(.json, 응답 보관 파일)으로 .txt, 알림장 이미지, 사진 카드를 다시 만든다.
+    /// - 서버에 요청하지 않고 지금의 config 설정([render])을 사용한다.
+    /// - 웹 페이지(export html)를 만든 적이 있으면 웹 페이지도 다시 만든다.
+    pub async fn run(
+        args: &RenderArgs,
+        mut event_handler: Option<Box<dyn BackupEventHandler>>,
+    ) -> RunSummary {
+        let mut summary = RunSummary::new("render");
+        let config = match KnBackupConfig::from_file(&args.config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.error(err.to_string());
+                return summary;
+            }
+        };
+        let mut download_args = DownloadArgs::from(args);
+        download_args.apply_config(&config.download_config(args.profile.as_deref()));
+        let theme = match config.render_theme() {
+            Ok(theme) => theme,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                summary.error(format!("render theme fail. {}", err));
+                return summary;
+            }
+        };
+
+        let output_dir = download_args.output_dir().to_string();
+        let _lock = match OutputLock::acquire(&output_dir, "render") {
+            Ok(lock) => lock,
+            Err(err) => {
+                log::error!(target:"lock", "{}", err);
+                summary.error(err);
+                return summary;
+            }
+        };
+
+        let layout = BackupLayout::new(&output_dir);
+        let raw_archive_dir = download_args.raw_archive_dir();
+        let archive =
+            Some(ResponseArchive::new(&raw_archive_dir)).filter(|_| raw_archive_dir.is_dir());
+        let local = layout.local_sources(archive.as_ref());
+        for error in local.errors {
+            log::error!(target:"render", "{}", error);
+            summary.error(error);
+        }
+        if local.sources.is_empty() {
+            log::warn!(target:"render", "no saved report. path={}", output_dir);
+            return summary;
+        }
+        log::info!(target:"render", "reports={}, path={}", local.sources.len(), output_dir);
+
+        // 다시 만들기 전의 파일 내용
+        let before: HashMap<PathBuf, u64> = local
+            .sources
+            .iter()
+            .flat_map(|f| Self::output_files(&layout, f, &theme))
+            .filter_map(|f| Self::file_hash(&f).map(|hash| (f, hash)))
+            .collect();
+
+        let export_dir = ExportCommand::export_dir(&output_dir, args.export_path.as_deref());
+        let export_sources = export_dir.is_dir().then(|| local.sources.clone());
+
+        let job = BackupJob::builder()
+            .layout(layout.clone())
+            .sinks(download_args.sinks())
+            .theme(theme)
+            .build();
+        let mut changes = RenderChanges::default();
+        let mut handler = |event: &BackupEvent| {
+            summary.on_event(event);
+            if let Some(event_handler) = event_handler.as_mut() {
+                event_handler.on_event(event);
+            }
+            if let BackupEvent::ContentSaved { path, .. } = event {
+                let path_buf = PathBuf::from(path);
+                match (before.get(&path_buf), Self::file_hash(&path_buf)) {
+                    (None, _) => changes.created.push(path.clone()),
+                    (Some(before), Some(after)) if *before == after => changes.unchanged += 1,
+                    _ => changes.changed.push(path.clone()),
+                }
+            }
+        };
+        job.render(&mut handler, local.sources);
+        changes.removed = before.keys().filter(|f| !f.exists()).cloned().collect();
+
+        for path in &changes.created {
+            log::info!(target:"render", "new. path={}", path);
+        }
+        for path in &changes.changed {
+            log::info!(target:"render", "changed. path={}", path);
+        }
+        for path in &changes.removed {
+            log::info!(target:"render", "removed. path={}", path.display());
+        }
+        log::info!(target:"render", "new={}, changed={}, unchanged={}, removed={}", changes.created.len(), changes.changed.len(), changes.unchanged, changes.removed.len());
+
+        if let Some(sources) = export_sources {
+            log::info!(target:"render", "rebuild website. path={}", export_dir.display());
+            ExportCommand::export_html(
+                layout,
+                &export_dir,
+                args.thumbnail_size,
+                &sources,
+                &mut summary,
+            );
+        }
+        summary
+    }
+
+    /// 다시 만들 수 있는 파일 (.txt, 알림장 이미지, 사진 카드)
+    fn output_files(
+        layout: &BackupLayout,
+        source: &BackupSource,
+        theme: &RenderTheme,
+    ) -> Vec<PathBuf> {
+        let mut files = vec![
+            layout.text_file(source),
+            layout.image_file(source, theme.format),
+            layout.card_file(source, theme.format, None),
+        ];
+        for page in 1.. {
+            let card_file = layout.card_file(source, theme.format, Some(page));
+            if !card_file.exists() {
+                break;
+            }
+            files.push(card_file);
+        }
+        files
+    }
+
+    /// 파일 내용 비교용 hash
+    fn file_hash(path: &Path) -> Option<u64> {
+        let bytes = fs::read(path).ok()?;
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use clap::Parser;
+use kidsnote_sdk::backup::{datatypes::BackupTarget, event::BackupEventHandler, BackupJob};
+
+use crate::{
+    command::download::{DownloadArgs, DownloadCommand},
+    state::{FailedItem, FailureKind},
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct RetryArgs {
+    #[command(flatten)]
+    pub download: DownloadArgs,
+}
+
+pub struct RetryCommand {
+    download: DownloadCommand,
+}
+
+impl RetryCommand {
+    /// init and run
+    pub async fn run(
+        args: &RetryArgs,
+        event_handler: Option<Box<dyn BackupEventHandler>>,
+    ) -> RunSummary {
+        if args.download.all_profiles {
+            let mut summary = RunSummary::new("retry");
+            summary.error(String::from("retry does not support --all-profiles."));
+            return summary;
+        }
+        let mut inst = Self {
+            download: DownloadCommand::new(&args.download, "retry", event_handler),
+        };
+        if let Some(_lock) = inst.download.lock_output() {
+            inst.next().await;
+        }
+        inst.download.summary
+    }
+
+    /// 실패 항목의 알림장을 다시 조회해서 받는다.
+    async fn next(&mut self) {
+        let failures = self.download.state.failures.clone();
+        if failures.is_empty() {
+            log::info!(target:"retry", "Nothing to retry.");
+            return;
+        }
+        log::info!(target:"retry", "retry start. count={}", failures.len());
+
+        let Some(me) = self.download.step_login().await else {
+            return;
+        };
+
+        let job = BackupJob::builder()
+            .output_dir(self.download.args.output_dir())
+            .theme(self.download.theme.clone())
+            .sinks(self.download.args.sinks())
+            .targets(Self::targets(&failures))
+            .fallbacks(self.download.state.fallback_sizes())
+            .children(me.children)
+            .build();
+        self.download.run_job(job).await;
+
+        log::info!(target:"retry", "retry end. remain={}", self.download.state.failures.len());
+    }
+
+    /// 실패 항목을 알림장 단위 백업 대상으로 변환
+    fn targets(failures: &[FailedItem]) -> Vec<BackupTarget> {
+        let mut groups: BTreeMap<u64, Vec<&FailedItem>> = BTreeMap::new();
+        for item in failures {
+            groups.entry(item.report_id).or_default().push(item);
+        }
+        groups
+            .into_values()
+            .map(|items| BackupTarget::Report {
+                child_id: items[0].child_id,
+                report_id: items[0].report_id,
+                report_date: items[0].report_date,
+                media_ids: Some(items.iter().filter_map(|f| f.media_id).collect()),
+                content: items.iter().any(|f| f.kind == FailureKind::Content),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,159 @@
+use chrono::{Duration, Local, Utc};
+use clap::Parser;
+
+use crate::{
+    command::download::{DownloadArgs, DownloadCommand},
+    kidsnote::KnBackupConfig,
+    state::{BackupState, WatchStatus},
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct StatusArgs {
+    /// Client ID
+    #[arg(
+        short = 'c',
+        long = "client_id",
+        env = "KNB_CLIENT_ID",
+        value_name = "Client id"
+    )]
+    pub client_id: Option<String>,
+
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml"
+    )]
+    pub config_path: String,
+
+    /// Output path [default: ./output]
+    #[arg(
+        short = 'o',
+        long = "output-path",
+        env = "KNB_OUTPUT_PATH",
+        value_name = "Output Path"
+    )]
+    pub output_dir: Option<String>,
+}
+
+impl From<&StatusArgs> for DownloadArgs {
+    fn from(args: &StatusArgs) -> Self {
+        let mut download_args = DownloadArgs::new();
+        download_args.client_id = args.client_id.clone();
+        download_args.profile = args.profile.clone();
+        download_args.config_path = args.config_path.clone();
+        download_args.output_dir = args.output_dir.clone();
+        download_args
+    }
+}
+
+pub struct StatusCommand {}
+
+impl StatusCommand {
+    /// 로그인 정보, 토큰 유효 여부, 자녀별 마지막 백업 출력
+    pub async fn run(args: &StatusArgs) -> RunSummary {
+        let profile_name = args.profile.as_deref().unwrap_or("default");
+        let config = match KnBackupConfig::from_file(&args.config_path) {
+            Ok(config) => config,
+            Err(err) => {
+                log::error!(target:"config", "{}", err);
+                let mut summary = RunSummary::new("status");
+                summary.error(err.to_string());
+                return summary;
+            }
+        };
+        let Some(profile) = config.profile(args.profile.as_deref()) else {
+            log::info!(target:"status", "[{}] not logged in. config={}", profile_name, args.config_path);
+            let mut summary = RunSummary::new("status");
+            summary.auth_fail(format!("[{}] not logged in.", profile_name));
+            return summary;
+        };
+        log::info!(target:"status", "[{}] user_id={}, encrypted={}", profile_name, profile.user_id.as_deref().unwrap_or(""), profile.encrypted_refresh_token.is_some());
+
+        let download_args = DownloadArgs::from(args);
+        let mut download = DownloadCommand::new(&download_args, "status", None);
+        if let Some(refresh_token) = download.args.refresh_token.clone() {
+            match download
+                .kidsnote_sdk
+                .auth()
+                .refresh_token(refresh_token.as_str())
+                .await
+            {
+                Ok(result) => {
+                    let expires_at = Utc::now()
+                        + Duration::try_seconds(result.expires_in as i64).unwrap_or_default();
+                    log::info!(target:"status", "token valid. access token expires in {}s ({})", result.expires_in, expires_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"));
+                }
+                Err(err) => {
+                    log::error!(target:"status", "token invalid. {}", err);
+                    download
+                        .summary
+                        .auth_fail(format!("token invalid. {}", err));
+                }
+            }
+        } else if !download.summary.auth_failed {
+            download
+                .summary
+                .auth_fail(String::from("no refresh token in config."));
+        }
+
+        let output_dir = download.args.output_dir();
+        let state = BackupState::from_dir(output_dir);
+        if state.children.is_empty() {
+            log::info!(target:"status", "no backup history. output={}", output_dir);
+        }
+        for (child_id, child) in &state.children {
+            let failures = state
+                .failures
+                .iter()
+                .filter(|f| f.child_id == *child_id)
+                .count();
+            log::info!(
+                target:"status",
+                "[{}] last sync={}, last run={}, failures={}",
+                child.child_name,
+                child
+                    .last_synced_at
+                    .map(|f| f.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or(String::from("-")),
+                child
+                    .last_run_at
+                    .map(|f| f.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or(String::from("-")),
+                failures
+            );
+        }
+        log::info!(target:"status", "failures={}, fallbacks={}", state.failures.len(), state.fallbacks.len());
+
+        if let Some(watch) = WatchStatus::from_dir(output_dir) {
+            let format_time = |time: Option<chrono::DateTime<Utc>>| {
+                time.map(|f| {
+                    f.with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or(String::from("-"))
+            };
+            log::info!(
+                target:"status",
+                "[watch] pid={}, state={}, runs={}, last run={}, last status={}, next run={}, failures={}",
+                watch.pid,
+                watch.state,
+                watch.runs,
+                format_time(watch.last_run_finished_at),
+                watch.last_status.as_deref().unwrap_or("-"),
+                format_time(watch.next_run_at),
+                watch.consecutive_failures
+            );
+            if let Some(last_error) = &watch.last_error {
+                log::info!(target:"status", "[watch] last error={}", last_error);
+            }
+        }
+        download.summary
+    }
+}
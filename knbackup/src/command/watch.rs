@@ -0,0 +1,239 @@
+use std::time::Duration;
+
+use chrono::{Local, Utc};
+use clap::Parser;
+use kidsnote_sdk::{
+    backup::{event::BackupEventHandler, BackupJob},
+    child::KidsnoteChildSdk,
+    user::datatypes::MeInfoResponse,
+};
+
+use crate::{
+    command::download::{DownloadArgs, DownloadCommand},
+    state::{BackupState, WatchStatus},
+    summary::{RunStatus, RunSummary},
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct WatchArgs {
+    #[command(flatten)]
+    pub download: DownloadArgs,
+
+    /// Time between runs (ex: 30m, 1h, 1day)
+    #[arg(long, default_value = "1h", value_parser = humantime::parse_duration)]
+    pub interval: Duration,
+
+    /// Random delay added to each interval
+    #[arg(long, default_value = "5m", value_parser = humantime::parse_duration)]
+    pub jitter: Duration,
+
+    /// First wait after a failed run. Doubles on each failure up to the interval.
+    #[arg(long = "retry-delay", default_value = "1m", value_parser = humantime::parse_duration)]
+    pub retry_delay: Duration,
+
+    /// Days to look back from the last sync
+    #[arg(long = "lookback-days", default_value = "7", value_name = "Days")]
+    pub lookback_days: i64,
+}
+
+pub struct WatchCommand {}
+
+impl WatchCommand {
+    /// 주기적으로 다운로드한다.
+    /// - 로그인 세션을 유지하고 매번 refresh token 으로 갱신한다.
+    /// - 종료 신호(SIGTERM, Ctrl-C)를 받으면 진행 중인 실행을 멈추고 상태를 저장한다.
+    pub async fn run(
+        args: &WatchArgs,
+        event_handler: Option<Box<dyn BackupEventHandler>>,
+    ) -> RunSummary {
+        let mut summary = RunSummary::new("watch");
+        if args.download.all_profiles {
+            summary.error(String::from("watch does not support --all-profiles."));
+            return summary;
+        }
+
+        let mut download = DownloadCommand::new(&args.download, "watch", event_handler);
+        if download.summary.auth_failed {
+            return download.summary;
+        }
+        let Some(_lock) = download.lock_output() else {
+            return download.summary;
+        };
+        let output_dir = download.args.output_dir().to_string();
+        let mut status = WatchStatus {
+            pid: std::process::id(),
+            started_at: Some(Utc::now()),
+            ..Default::default()
+        };
+        log::info!(target:"watch", "watch start. interval={}, output={}", humantime::format_duration(args.interval), output_dir);
+
+        let shutdown = Self::shutdown_signal();
+        tokio::pin!(shutdown);
+        loop {
+            status.state = String::from("running");
+            status.runs += 1;
+            status.last_run_started_at = Some(Utc::now());
+            status.next_run_at = None;
+            status.save(&output_dir);
+
+            download.summary = RunSummary::new("watch");
+            // 파일은 받은 뒤 한 번에 쓰기 때문에 중간에 멈춰도 깨진 파일이 남지 않는다.
+            let aborted = tokio::select! {
+                _ = Self::run_once(&mut download, args.lookback_days) => false,
+                _ = &mut shutdown => true,
+            };
+            if aborted {
+                log::warn!(target:"watch", "stop signal. in-flight run aborted.");
+                download.state.save(&output_dir);
+                status.last_status = Some(String::from("Aborted"));
+                status.last_run_finished_at = Some(Utc::now());
+                break;
+            }
+
+            download.summary.finish();
+            download.summary.print();
+            let run_status = download.summary.status();
+            status.last_status = Some(format!("{:?}", run_status));
+            status.last_error = download.summary.errors.last().cloned();
+            status.last_run_finished_at = download.summary.finished_at;
+            let mut run_summary = download.summary.clone();
+            run_summary.errors.clear();
+            summary.merge(run_summary);
+
+            // refresh token 이 만료되었거나 폐기된 경우는 다시 로그인해야 한다.
+            if run_status == RunStatus::AuthFailure {
+                log::error!(target:"watch", "login fail. watch stopped. login again.");
+                summary.auth_fail(status.last_error.clone().unwrap_or_default());
+                break;
+            }
+
+            // 서버 오류로 일부만 받은 경우도 기다렸다가 다시 시도한다.
+            let delay = if download.summary.is_retryable_failure() {
+                status.consecutive_failures += 1;
+                Self::backoff(args, status.consecutive_failures)
+            } else {
+                status.consecutive_failures = 0;
+                args.interval + Self::jitter(args.jitter)
+            };
+            let next_run_at = Utc::now() + chrono::Duration::from_std(delay).unwrap_or_default();
+            status.state = String::from("waiting");
+            status.next_run_at = Some(next_run_at);
+            status.save(&output_dir);
+            log::info!(target:"watch", "next run at {}. failures={}", next_run_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"), status.consecutive_failures);
+
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = &mut shutdown => {
+                    log::info!(target:"watch", "stop signal.");
+                    break;
+                }
+            }
+        }
+
+        status.state = String::from("stopped");
+        status.next_run_at = None;
+        status.save(&output_dir);
+        log::info!(target:"watch", "watch stopped. runs={}", status.runs);
+        summary
+    }
+
+    /// 로그인 후 마지막 백업 이후의 알림장을 받는다.
+    async fn run_once(download: &mut DownloadCommand, lookback_days: i64) {
+        // 갱신되어 바뀐 refresh token 으로 로그인
+        if let Some(refresh_token) = download
+            .kidsnote_sdk
+            .get_options_clone()
+            .get_refresh_token()
+        {
+            download.args.refresh_token = Some(refresh_token);
+        }
+        download.seen_reports.clear();
+        let Some(me) = download.step_login().await else {
+            return;
+        };
+
+        let (date_start, date_end, incremental) = match &download.args.date_start {
+            Some(date_start) => (
+                Some(date_start.clone()),
+                download.args.date_end.clone(),
+                false,
+            ),
+            None => match Self::incremental_start(&download.state, &me, lookback_days) {
+                Some(date_start) => {
+                    log::info!(target:"watch", "incremental sync from {}", date_start);
+                    (
+                        Some(date_start),
+                        Some(KidsnoteChildSdk::report_local_date(Utc::now())),
+                        true,
+                    )
+                }
+                None => (None, download.args.date_end.clone(), false),
+            },
+        };
+        let job = BackupJob::builder()
+            .output_dir(download.args.output_dir())
+            .date_start(date_start)
+            .date_end(date_end)
+            .incremental(incremental)
+            .dry_run(download.args.is_test())
+            .sinks(download.args.sinks())
+            .fallbacks(download.state.fallback_sizes())
+            .theme(download.theme.clone())
+            .children(me.children)
+            .build();
+        download.run_job(job).await;
+    }
+
+    /// 모든 자녀가 한 번 이상 백업되었으면 가장 오래된 마지막 백업에서 lookback 만큼 앞부터 조회한다.
+    fn incremental_start(
+        state: &BackupState,
+        me: &MeInfoResponse,
+        lookback_days: i64,
+    ) -> Option<String> {
+        let last_synced_at = me
+            .children
+            .iter()
+            .map(|child| state.children.get(&child.id).and_then(|f| f.last_synced_at))
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min()?;
+        let lookback = chrono::Duration::try_days(lookback_days).unwrap_or_default();
+        Some(KidsnoteChildSdk::report_local_date(
+            last_synced_at - lookback,
+        ))
+    }
+
+    /// 실패 횟수마다 두 배씩 늘린다. interval 보다 길어지지 않는다.
+    fn backoff(args: &WatchArgs, failures: u32) -> Duration {
+        let factor = 1u32 << failures.saturating_sub(1).min(16);
+        args.retry_delay.saturating_mul(factor).min(args.interval)
+    }
+
+    fn jitter(max: Duration) -> Duration {
+        Duration::from_millis(fastrand::u64(0..=max.as_millis() as u64))
+    }
+
+    /// SIGTERM, Ctrl-C
+    async fn shutdown_signal() {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            match signal(SignalKind::terminate()) {
+                Ok(mut terminate) => {
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => {}
+                        _ = terminate.recv() => {}
+                    }
+                }
+                Err(err) => {
+                    log::error!(target:"watch", "SIGTERM handler fail. {}", err);
+                    let _ = tokio::signal::ctrl_c().await;
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use clap::Parser;
+
+use crate::{
+    command::download::{DownloadArgs, DownloadCommand},
+    summary::RunSummary,
+};
+
+#[derive(Parser, Debug, Clone)]
+pub struct WhoamiArgs {
+    /// Client ID
+    #[arg(
+        short = 'c',
+        long = "client_id",
+        env = "KNB_CLIENT_ID",
+        value_name = "Client id"
+    )]
+    pub client_id: Option<String>,
+
+    /// Profile name in the config file
+    #[arg(long = "profile", env = "KNB_PROFILE", value_name = "Profile Name")]
+    pub profile: Option<String>,
+
+    // Sets a custom config file
+    #[arg(
+        long = "config",
+        value_name = "Config File Path",
+        default_value = "~/.knbackup/config.toml"
+    )]
+    pub config_path: String,
+}
+
+impl From<&WhoamiArgs> for DownloadArgs {
+    fn from(args: &WhoamiArgs) -> Self {
+        let mut download_args = DownloadArgs::new();
+        download_args.client_id = args.client_id.clone();
+        download_args.profile = args.profile.clone();
+        download_args.config_path = args.config_path.clone();
+        download_args
+    }
+}
+
+pub struct WhoamiCommand {}
+
+impl WhoamiCommand {
+    /// 저장된 로그인 정보로 내정보를 조회해서 출력
+    pub async fn run(args: &WhoamiArgs) -> RunSummary {
+        let download_args = DownloadArgs::from(args);
+        let mut download = DownloadCommand::new(&download_args, "whoami", None);
+        let Some(me) = download.step_login().await else {
+            return download.summary;
+        };
+
+        let user = &me.user;
+        log::info!(target:"whoami", "profile={}", args.profile.as_deref().unwrap_or("default"));
+        log::info!(target:"whoami", "user. id={}, username={}, name={}, type={}", user.id, user.username, user.name, user.r#type);
+        log::info!(target:"whoami", "email={}, phone={}, date_joined={}", user.email, user.phone, user.date_joined);
+        for child in &me.children {
+            log::info!(target:"whoami", "child. id={}, name={}, birth={}", child.id, child.name, child.date_birth);
+            for enroll in &child.enrollment {
+                log::info!(target:"whoami", "  enrollment. center={}, class={}, approved={}", enroll.center_name, enroll.class_name, enroll.is_approved);
+            }
+        }
+        download.summary
+    }
+}
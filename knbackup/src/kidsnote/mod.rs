@@ -1,24 +1,181 @@
-use std::{
-    fs::{self, File},
-    io::Write,
-    path::PathBuf,
-};
+use std::{collections::BTreeMap, env, fs, io::IsTerminal, path::PathBuf};
 
+use kidsnote_sdk::{
+    auth::error_types::AuthError,
+    token_store::{datatypes::StoredToken, file::write_private, secret, TokenStore},
+    tool::render_theme::RenderTheme,
+};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// refresh token 암호화 passphrase 환경 변수
+pub const PASSPHRASE_ENV: &str = "KNB_PASSPHRASE";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct KnBackupConfig {
     pub default: Option<KidsnoteConfigProfile>,
+    /// 이름 있는 계정 ([profiles.mom], [profiles.dad])
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, KidsnoteConfigProfile>,
+    /// download 기본 옵션 ([download])
+    #[serde(default, skip_serializing_if = "DownloadConfig::is_empty")]
+    pub download: DownloadConfig,
+    /// 알림장 이미지 테마 ([render]). preset 에 나머지 값을 덮어쓴다.
+    #[serde(default, skip_serializing_if = "toml::Table::is_empty")]
+    pub render: toml::Table,
+    /// 입력 받은 passphrase. 한 번만 묻는다.
+    #[serde(skip)]
+    passphrase: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct KidsnoteConfigProfile {
     pub user_id: Option<String>,
+    /// 평문 refresh token (이전 버전 호환, passphrase 가 없을 때)
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub refresh_token: Option<String>,
+    /// passphrase 로 암호화한 refresh token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_refresh_token: Option<String>,
+    /// refresh token 을 암호화하지 않고 저장 (login --plaintext-token)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub plaintext_token: bool,
+    /// 계정별 download 옵션 ([profiles.mom.download]). [download] 보다 우선한다.
+    #[serde(default, skip_serializing_if = "DownloadConfig::is_empty")]
+    pub download: DownloadConfig,
+}
+
+/// refresh token 저장 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenStorage {
+    /// 암호화하지 않는다. (login --plaintext-token)
+    Plaintext,
+    /// passphrase 로 암호화한다. passphrase 가 없으면 오류
+    Encrypted,
+    /// 저장된 방식을 유지한다. 평문으로 저장된 계정도 passphrase 가 있으면 암호화한다. (토큰 갱신)
+    Saved,
+}
+
+impl KidsnoteConfigProfile {
+    /// refresh token 을 평문으로 저장한 계정 (login --plaintext-token, 이전 버전)
+    pub fn is_plaintext(&self) -> bool {
+        self.plaintext_token
+            || (self.refresh_token.is_some() && self.encrypted_refresh_token.is_none())
+    }
+}
+
+/// config 파일의 download 옵션
+/// - 우선 순위 : 명령행 > 환경 변수 > 계정별 > [download]
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DownloadConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_start: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test: Option<bool>,
+    /// 알림장 내용과 사진을 함께 그린 카드도 만든다.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report_card: Option<bool>,
+    /// API 응답 원문을 보관한다.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archive_raw: Option<bool>,
+}
+
+impl DownloadConfig {
+    /// config get/set 에서 사용하는 이름
+    pub const KEYS: [&'static str; 7] = [
+        "client_id",
+        "output_dir",
+        "date_start",
+        "date_end",
+        "test",
+        "report_card",
+        "archive_raw",
+    ];
+
+    /// 아무 것도 지정하지 않았을 때의 값
+    pub fn defaults() -> Self {
+        Self {
+            output_dir: Some(String::from("./output")),
+            test: Some(false),
+            report_card: Some(false),
+            archive_raw: Some(false),
+            ..Default::default()
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 비어있는 값을 lower 의 값으로 채운다.
+    pub fn or(self, lower: &DownloadConfig) -> DownloadConfig {
+        DownloadConfig {
+            client_id: self.client_id.or_else(|| lower.client_id.clone()),
+            output_dir: self.output_dir.or_else(|| lower.output_dir.clone()),
+            date_start: self.date_start.or_else(|| lower.date_start.clone()),
+            date_end: self.date_end.or_else(|| lower.date_end.clone()),
+            test: self.test.or(lower.test),
+            report_card: self.report_card.or(lower.report_card),
+            archive_raw: self.archive_raw.or(lower.archive_raw),
+        }
+    }
+
+    pub fn get(&self, key: &str) -> Result<Option<String>, AuthError> {
+        match key {
+            "client_id" => Ok(self.client_id.clone()),
+            "output_dir" => Ok(self.output_dir.clone()),
+            "date_start" => Ok(self.date_start.clone()),
+            "date_end" => Ok(self.date_end.clone()),
+            "test" => Ok(self.test.map(|f| f.to_string())),
+            "report_card" => Ok(self.report_card.map(|f| f.to_string())),
+            "archive_raw" => Ok(self.archive_raw.map(|f| f.to_string())),
+            _ => Err(Self::unknown_key(key)),
+        }
+    }
+
+    /// 값이 없으면 지운다.
+    pub fn set(&mut self, key: &str, value: Option<&str>) -> Result<(), AuthError> {
+        let value = value.map(str::to_string);
+        match key {
+            "client_id" => self.client_id = value,
+            "output_dir" => self.output_dir = value,
+            "date_start" => self.date_start = value,
+            "date_end" => self.date_end = value,
+            "test" => self.test = Self::parse_bool(key, value)?,
+            "report_card" => self.report_card = Self::parse_bool(key, value)?,
+            "archive_raw" => self.archive_raw = Self::parse_bool(key, value)?,
+            _ => return Err(Self::unknown_key(key)),
+        }
+        Ok(())
+    }
+
+    fn parse_bool(key: &str, value: Option<String>) -> Result<Option<bool>, AuthError> {
+        match value {
+            Some(value) => value.parse().map(Some).map_err(|_| {
+                AuthError::GeneralErrorStr(format!("invalid value. {}={} (true|false)", key, value))
+            }),
+            None => Ok(None),
+        }
+    }
+
+    fn unknown_key(key: &str) -> AuthError {
+        AuthError::GeneralErrorStr(format!(
+            "unknown config key. key={} ({})",
+            key,
+            Self::KEYS.join("|")
+        ))
+    }
 }
 
 impl KnBackupConfig {
-    pub fn from_file(config_path: &str) -> KnBackupConfig {
+    /// config 파일 읽기. 파일이 없으면 기본값
+    /// - 읽을 수 없는 파일은 오류를 돌려준다. 기본값으로 덮어써서 계정 정보를 잃지 않도록
+    pub fn from_file(config_path: &str) -> Result<KnBackupConfig, AuthError> {
         let config_path = if let Some(config_path) = config_path.strip_prefix("~/") {
             let next_path = dirs::home_dir().unwrap_or(PathBuf::from("./"));
             next_path.join(config_path)
@@ -26,23 +183,215 @@ impl KnBackupConfig {
             PathBuf::from(config_path)
         };
 
-        if let Ok(toml_string) = fs::read_to_string(config_path) {
-            if let Ok(config) = toml::from_str(&toml_string) {
-                return config;
+        let toml_string = match fs::read_to_string(&config_path) {
+            Ok(toml_string) => toml_string,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(KnBackupConfig::default())
+            }
+            Err(err) => {
+                return Err(AuthError::GeneralErrorStr(format!(
+                    "config file read fail. path={}, {}",
+                    config_path.display(),
+                    err
+                )))
             }
+        };
+        toml::from_str(&toml_string).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "config file parse fail. path={}, {}",
+                config_path.display(),
+                err
+            ))
+        })
+    }
+
+    /// 계정 정보. 이름이 없으면 default
+    pub fn profile(&self, name: Option<&str>) -> Option<&KidsnoteConfigProfile> {
+        match name {
+            Some(name) => self.profiles.get(name),
+            None => self.default.as_ref(),
         }
+    }
+
+    /// 계정별 download 옵션에 [download] 를 합친 값
+    pub fn download_config(&self, name: Option<&str>) -> DownloadConfig {
+        match self.profile(name) {
+            Some(profile) => profile.download.clone().or(&self.download),
+            None => self.download.clone(),
+        }
+    }
 
-        KnBackupConfig { default: None }
+    /// [render] 의 알림장 이미지 테마
+    pub fn render_theme(&self) -> Result<RenderTheme, AuthError> {
+        let mut render = self.render.clone();
+        let preset = match render.remove("preset") {
+            Some(toml::Value::String(preset)) => preset,
+            Some(preset) => {
+                return Err(AuthError::GeneralErrorStr(format!(
+                    "invalid render preset. preset={}",
+                    preset
+                )))
+            }
+            None => String::from("default"),
+        };
+        let theme = RenderTheme::preset(&preset).ok_or_else(|| {
+            AuthError::GeneralErrorStr(format!(
+                "unknown render preset. preset={} ({})",
+                preset,
+                RenderTheme::PRESETS.join("|")
+            ))
+        })?;
+        let mut value = toml::Value::try_from(theme)
+            .map_err(|err| AuthError::GeneralErrorStr(format!("render theme fail. {}", err)))?;
+        merge_toml(&mut value, toml::Value::Table(render));
+        let theme: RenderTheme = value
+            .try_into()
+            .map_err(|err| AuthError::GeneralErrorStr(format!("invalid [render]. {}", err)))?;
+        theme.validate()?;
+        Ok(theme)
     }
 
-    pub fn set_default(&mut self, user_id: String, refresh_token: String) {
-        self.default = Some(KidsnoteConfigProfile {
+    /// 로그인에 사용할 계정 정보. 암호화된 refresh token 은 passphrase 로 풀어서 돌려준다.
+    pub fn login_profile(
+        &mut self,
+        name: Option<&str>,
+    ) -> Result<Option<KidsnoteConfigProfile>, AuthError> {
+        let Some(mut profile) = self.profile(name).cloned() else {
+            return Ok(None);
+        };
+        let Some(encrypted_refresh_token) = profile.encrypted_refresh_token.take() else {
+            return Ok(Some(profile));
+        };
+        let Some(passphrase) = self.passphrase(false) else {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "refresh token is encrypted. passphrase required. ({})",
+                PASSPHRASE_ENV
+            )));
+        };
+        match secret::decrypt(&passphrase, &encrypted_refresh_token) {
+            Ok(refresh_token) => {
+                profile.refresh_token = Some(refresh_token);
+                Ok(Some(profile))
+            }
+            Err(err) => {
+                // 다음에 다시 묻도록
+                self.passphrase = None;
+                Err(err)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn set_passphrase(&mut self, passphrase: Option<String>) {
+        self.passphrase = passphrase;
+    }
+
+    /// 입력 받았거나 환경 변수에 있는 passphrase 가 있는지. 터미널에서 묻지 않는다.
+    fn has_passphrase(&self) -> bool {
+        self.passphrase.is_some() || env::var(PASSPHRASE_ENV).is_ok_and(|f| !f.is_empty())
+    }
+
+    /// 환경 변수, 없으면 터미널에서 입력 받는다.
+    fn passphrase(&mut self, confirm: bool) -> Option<String> {
+        if self.passphrase.is_none() {
+            self.passphrase = env::var(PASSPHRASE_ENV)
+                .ok()
+                .filter(|f| !f.is_empty())
+                .or_else(|| Self::prompt_passphrase(confirm));
+        }
+        self.passphrase.clone()
+    }
+
+    fn prompt_passphrase(confirm: bool) -> Option<String> {
+        if !std::io::stdin().is_terminal() {
+            return None;
+        }
+        let passphrase = rpassword::prompt_password("Config passphrase: ").ok()?;
+        if confirm {
+            let passphrase_again =
+                rpassword::prompt_password("Config passphrase (again): ").ok()?;
+            if passphrase != passphrase_again {
+                log::error!(target:"config", "passphrase does not match.");
+                return None;
+            }
+        }
+        Some(passphrase).filter(|f| !f.is_empty())
+    }
+
+    /// 계정 정보 저장. 이름이 없으면 default
+    /// - refresh token 은 passphrase 로 암호화해서 저장한다. passphrase 가 없거나 암호화에 실패하면 오류
+    /// - Plaintext 는 암호화하지 않는다. Saved 는 평문으로 저장된 계정이고 passphrase 가 없을 때만 평문으로 저장한다.
+    /// - 암호화하면 평문 refresh token 은 지운다. 계정별 download 옵션은 유지한다.
+    pub fn set_profile(
+        &mut self,
+        name: Option<&str>,
+        user_id: String,
+        refresh_token: String,
+        storage: TokenStorage,
+    ) -> Result<(), AuthError> {
+        let saved = self.profile(name);
+        let plaintext_token = match storage {
+            TokenStorage::Plaintext => true,
+            TokenStorage::Encrypted => false,
+            TokenStorage::Saved => {
+                saved.is_some_and(KidsnoteConfigProfile::is_plaintext) && !self.has_passphrase()
+            }
+        };
+        let mut profile = KidsnoteConfigProfile {
             user_id: Some(user_id),
-            refresh_token: Some(refresh_token),
-        });
+            plaintext_token,
+            download: saved.map(|f| f.download.clone()).unwrap_or_default(),
+            ..Default::default()
+        };
+        if plaintext_token {
+            log::warn!(target:"config", "refresh token is saved as plain text. login again without --plaintext-token to encrypt it.");
+            profile.refresh_token = Some(refresh_token);
+        } else {
+            let Some(passphrase) = self.passphrase(true) else {
+                return Err(AuthError::GeneralErrorStr(format!(
+                    "passphrase required to save the refresh token. set {} or use login --plaintext-token",
+                    PASSPHRASE_ENV
+                )));
+            };
+            profile.encrypted_refresh_token = Some(secret::encrypt(&passphrase, &refresh_token)?);
+        }
+        match name {
+            Some(name) => {
+                self.profiles.insert(name.to_string(), profile);
+            }
+            None => self.default = Some(profile),
+        }
+        Ok(())
     }
 
-    pub fn save(&self, save_path: String) {
+    /// config 파일의 계정을 token store 로 사용
+    /// - storage 방식으로 refresh token 을 저장한다.
+    pub fn into_token_store(
+        self,
+        name: Option<String>,
+        config_path: String,
+        storage: TokenStorage,
+    ) -> Box<dyn TokenStore> {
+        Box::new(ConfigTokenStore {
+            config: self,
+            name,
+            config_path,
+            storage,
+        })
+    }
+
+    /// 저장된 계정 이름 목록. default 는 None
+    pub fn profile_names(&self) -> Vec<Option<String>> {
+        let mut names = Vec::new();
+        if self.default.is_some() {
+            names.push(None);
+        }
+        names.extend(self.profiles.keys().cloned().map(Some));
+        names
+    }
+
+    /// config 파일 저장 (0600)
+    pub fn save(&self, save_path: String) -> Result<(), AuthError> {
         let config_path = if let Some(config_path) = save_path.strip_prefix("~/") {
             let next_path = dirs::home_dir().unwrap_or(PathBuf::from("./"));
             next_path.join(config_path)
@@ -57,18 +406,103 @@ impl KnBackupConfig {
             }
         }
 
-        let toml_string = toml::to_string(&self).unwrap();
-        match File::create(config_path) {
-            Ok(mut file) => {
-                if let Err(err) = file.write_all(toml_string.as_bytes()) {
-                    log::error!(target:"config", "config file save fail. {}", err);
-                } else {
-                    log::info!(target:"config", "config file save. path={}", save_path);
+        // 임시 파일에 쓰고 교체 (0600)
+        let toml_string = toml::to_string(&self)
+            .map_err(|err| AuthError::GeneralErrorStr(format!("config serialize fail. {}", err)))?;
+        write_private(&config_path, toml_string.as_bytes()).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "config file save fail. path={}, {}",
+                save_path, err
+            ))
+        })?;
+        log::info!(target:"config", "config file save. path={}", save_path);
+        Ok(())
+    }
+}
+
+/// 표의 값을 재귀적으로 덮어쓴다.
+/// - 실수 값에 정수를 쓴 경우(size = 30) 실수로 바꾼다.
+fn merge_toml(base: &mut toml::Value, value: toml::Value) {
+    match (base, value) {
+        (toml::Value::Table(base), toml::Value::Table(value)) => {
+            for (key, value) in value {
+                match base.get_mut(&key) {
+                    Some(base) => merge_toml(base, value),
+                    None => {
+                        base.insert(key, value);
+                    }
                 }
             }
-            Err(err) => {
-                log::error!(target:"config", "config file create fail. {}", err);
+        }
+        (base @ toml::Value::Float(_), toml::Value::Integer(value)) => {
+            *base = toml::Value::Float(value as f64);
+        }
+        (base, value) => *base = value,
+    }
+}
+
+/// config 파일의 계정 하나를 저장소로 사용
+/// - 새 refresh token 을 받을 때마다 config 파일을 다시 읽어 이 계정의 token 만 바꾸고 저장한다.
+///   (실행 중에 다른 명령이 바꾼 config 를 덮어쓰지 않도록)
+struct ConfigTokenStore {
+    config: KnBackupConfig,
+    name: Option<String>,
+    config_path: String,
+    storage: TokenStorage,
+}
+
+impl ConfigTokenStore {
+    /// 지금의 config 파일. 입력 받은 passphrase 는 유지한다.
+    fn reload(&self) -> Result<KnBackupConfig, AuthError> {
+        let mut config = KnBackupConfig::from_file(&self.config_path)?;
+        config.passphrase = self.config.passphrase.clone();
+        Ok(config)
+    }
+}
+
+impl TokenStore for ConfigTokenStore {
+    fn load(&mut self) -> Result<Option<StoredToken>, AuthError> {
+        let profile = self.config.login_profile(self.name.as_deref())?;
+        Ok(profile.and_then(|profile| {
+            profile.refresh_token.map(|refresh_token| StoredToken {
+                user_id: profile.user_id,
+                refresh_token,
+            })
+        }))
+    }
+
+    fn save(&mut self, token: &StoredToken) -> Result<(), AuthError> {
+        let mut config = self.reload()?;
+        let user_id = token
+            .user_id
+            .clone()
+            .or_else(|| {
+                config
+                    .profile(self.name.as_deref())
+                    .and_then(|f| f.user_id.clone())
+            })
+            .unwrap_or_default();
+        config.set_profile(
+            self.name.as_deref(),
+            user_id,
+            token.refresh_token.clone(),
+            self.storage,
+        )?;
+        config.save(self.config_path.clone())?;
+        self.config = config;
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), AuthError> {
+        let mut config = self.reload()?;
+        match &self.name {
+            Some(name) => {
+                config.profiles.remove(name);
             }
+            None => config.default = None,
         }
+        config.save(self.config_path.clone())?;
+        self.config = config;
+        Ok(())
     }
 }
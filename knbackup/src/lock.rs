@@ -0,0 +1,139 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Local, Utc};
+use fs4::fs_std::FileExt;
+use serde::{Deserialize, Serialize};
+
+/// 출력 폴더 안의 잠금 파일
+const LOCK_FILE: &str = ".knbackup/lock";
+
+/// 잠금 파일 내용
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LockInfo {
+    pub pid: u32,
+    pub host: String,
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+}
+
+/// 같은 출력 폴더에 동시에 받지 않도록 잠근다.
+/// - 잠금 파일에 OS 파일 잠금(flock)을 건다. 프로세스가 비정상 종료해도 잠금은 OS 가 풀어준다.
+/// - 잠금 파일에는 누가 잠갔는지 남긴다. 끝나면 잠금 파일을 지운다.
+#[derive(Debug)]
+pub struct OutputLock {
+    path: PathBuf,
+    file: File,
+}
+
+impl OutputLock {
+    pub fn lock_path(output_dir: &str) -> PathBuf {
+        Path::new(output_dir).join(LOCK_FILE)
+    }
+
+    pub fn acquire(output_dir: &str, command: &str) -> Result<OutputLock, String> {
+        let path = Self::lock_path(output_dir);
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir)
+                .map_err(|err| format!("lock dir create fail. {}", err))?;
+        }
+        let info = LockInfo {
+            pid: std::process::id(),
+            host: host_name(),
+            command: command.to_string(),
+            started_at: Utc::now(),
+        };
+
+        // 잠근 사이에 다른 실행이 잠금 파일을 지우고 끝났으면 새 파일로 다시 시도
+        for _ in 0..3 {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .map_err(|err| format!("lock file open fail. {}", err))?;
+            let locked = FileExt::try_lock_exclusive(&file)
+                .map_err(|err| format!("lock fail. path={}, {}", path.display(), err))?;
+            if !locked {
+                return Err(Self::locked_error(&path));
+            }
+            if !is_same_file(&file, &path) {
+                continue;
+            }
+            if let Ok(metadata) = file.metadata() {
+                if metadata.len() > 0 {
+                    log::warn!(target:"lock", "previous run did not finish cleanly. path={}", path.display());
+                }
+            }
+            let json_string = serde_json::to_string_pretty(&info).unwrap();
+            file.set_len(0)
+                .and_then(|_| file.write_all(json_string.as_bytes()))
+                .map_err(|err| format!("lock file write fail. {}", err))?;
+            return Ok(OutputLock { path, file });
+        }
+        Err(format!("lock file create fail. path={}", path.display()))
+    }
+
+    /// 다른 실행이 잠근 경우
+    fn locked_error(path: &Path) -> String {
+        let holder = fs::read_to_string(path)
+            .ok()
+            .and_then(|f| serde_json::from_str::<LockInfo>(&f).ok());
+        match holder {
+            Some(holder) => format!(
+                "output path is used by another run. pid={}, host={}, command={}, started={}.",
+                holder.pid,
+                holder.host,
+                holder.command,
+                holder
+                    .started_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+            ),
+            None => format!(
+                "output path is used by another run. path={}",
+                path.display()
+            ),
+        }
+    }
+}
+
+impl Drop for OutputLock {
+    /// 잠금을 풀기 전에 지운다. (잠금 파일이 없어진 것을 본 다른 실행은 새 파일로 다시 잠근다)
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            log::error!(target:"lock", "lock file remove fail. {}", err);
+        }
+        if let Err(err) = FileExt::unlock(&self.file) {
+            log::error!(target:"lock", "unlock fail. {}", err);
+        }
+    }
+}
+
+fn host_name() -> String {
+    hostname::get()
+        .ok()
+        .and_then(|f| f.into_string().ok())
+        .filter(|f| !f.is_empty())
+        .unwrap_or(String::from("localhost"))
+}
+
+/// 잠근 파일이 아직 잠금 파일 경로에 있는지
+#[cfg(unix)]
+fn is_same_file(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (file.metadata(), fs::metadata(path)) {
+        (Ok(locked), Ok(current)) => locked.dev() == current.dev() && locked.ino() == current.ino(),
+        _ => false,
+    }
+}
+
+/// 열려 있는 파일은 지울 수 없어서 항상 같다.
+#[cfg(not(unix))]
+fn is_same_file(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
@@ -1,12 +1,44 @@
-use std::env;
+use std::{
+    env,
+    io::{self, Write},
+};
 
+use indicatif::MultiProgress;
 use log4rs::{
     append::console::ConsoleAppender,
     config::{Appender, Root},
+    encode::{pattern::PatternEncoder, writer::simple::SimpleWriter, Encode},
     Config,
 };
 
-pub fn init() {
+const LOG_PATTERN: &str = "[{l}][{d(%H:%M:%S)}][{T}][{t}] {m}{n}";
+
+/// 진행 막대가 깨지지 않도록 막대를 잠시 지우고 로그를 출력
+#[derive(Debug)]
+struct ProgressAppender {
+    multi: MultiProgress,
+    encoder: PatternEncoder,
+}
+
+impl log::Log for ProgressAppender {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        let mut writer = SimpleWriter(Vec::new());
+        if self.encoder.encode(&mut writer, record).is_ok() {
+            self.multi.suspend(|| {
+                let _ = io::stdout().write_all(&writer.0);
+            });
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// progress 가 있으면 진행 막대와 함께 출력한다.
+pub fn init(progress: Option<&MultiProgress>) {
     let log_level = if let Ok(rust_log) = env::var("RUST_LOG"){
         match rust_log.to_lowercase().as_str() {
             "off" => log::LevelFilter::Off,
@@ -23,14 +55,21 @@ pub fn init() {
 
     let root = Root::builder().appender("stdout").build(log_level);
 
-    let console_appender = ConsoleAppender::builder()
-        .encoder(Box::new(log4rs::encode::pattern::PatternEncoder::new(
-            "[{l}][{d(%H:%M:%S)}][{T}][{t}] {m}{n}",
-        )))
-        .build();
+    let stdout_appender: Box<dyn log4rs::append::Append> = if let Some(multi) = progress {
+        Box::new(ProgressAppender {
+            multi: multi.clone(),
+            encoder: PatternEncoder::new(LOG_PATTERN),
+        })
+    } else {
+        Box::new(
+            ConsoleAppender::builder()
+                .encoder(Box::new(PatternEncoder::new(LOG_PATTERN)))
+                .build(),
+        )
+    };
 
     let config = Config::builder()
-        .appender(Appender::builder().build("stdout", Box::new(console_appender)))
+        .appender(Appender::builder().build("stdout", stdout_appender))
         .build(root)
         .unwrap();
 
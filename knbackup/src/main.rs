@@ -1,11 +1,25 @@
 mod command;
 mod kidsnote;
+mod lock;
 mod logger;
+mod progress;
+mod state;
+mod summary;
 
-use std::env;
+use std::{env, process::ExitCode};
 
+use crate::command::config::ConfigCommand;
 use crate::command::download::DownloadCommand;
+use crate::command::export::ExportCommand;
+use crate::command::list::ListCommand;
 use crate::command::login::LoginCommand;
+use crate::command::logout::LogoutCommand;
+use crate::command::render::RenderCommand;
+use crate::command::retry::RetryCommand;
+use crate::command::status::StatusCommand;
+use crate::command::watch::WatchCommand;
+use crate::command::whoami::WhoamiCommand;
+use crate::progress::ProgressMode;
 use clap::{Parser, Subcommand};
 
 #[derive(Parser, Debug)]
@@ -16,6 +30,12 @@ use clap::{Parser, Subcommand};
 pub struct Cli {
     #[clap(long, global = true)]
     debug: bool,
+    /// Write the run summary as JSON
+    #[clap(long = "summary-json", global = true, value_name = "Summary File Path")]
+    summary_json: Option<String>,
+    /// Progress display
+    #[clap(long, global = true, value_enum, default_value = "auto")]
+    progress: ProgressMode,
     #[clap(subcommand)]
     pub command: CliCommand,
 }
@@ -24,21 +44,72 @@ pub struct Cli {
 pub enum CliCommand {
     Login(crate::command::login::LoginArgs),
     Download(crate::command::download::DownloadArgs),
+    /// List children, enrollments and monthly report counts
+    List(crate::command::list::ListArgs),
+    /// Retry failed items saved in the output path
+    Retry(crate::command::retry::RetryArgs),
+    /// Remove the saved login from the config file
+    Logout(crate::command::logout::LogoutArgs),
+    /// Show the account of the saved login
+    Whoami(crate::command::whoami::WhoamiArgs),
+    /// Show token validity and last backup per child
+    Status(crate::command::status::StatusArgs),
+    /// Download repeatedly on a schedule
+    Watch(crate::command::watch::WatchArgs),
+    /// Show or change download options in the config file
+    Config(crate::command::config::ConfigArgs),
+    /// Re-render text, report images and cards from saved reports without the server
+    Render(crate::command::render::RenderArgs),
+    /// Export saved reports and photos to another format
+    Export(crate::command::export::ExportArgs),
 }
 
 #[tokio::main]
-async fn main() {
+async fn main() -> ExitCode {
     let cli = Cli::parse();
     if cli.debug {
         env::set_var("RUST_LOG", "debug");
     }
-    logger::init();
+    let progress_mode = cli.progress.resolve();
+    let multi_progress = progress::create_multi_progress(progress_mode);
+    logger::init(multi_progress.as_ref());
+    // 진행 표시와 실행 요약은 백업 명령에서만 쓴다. (비밀번호 입력, 명령 출력을 덮어쓰지 않게)
+    let backup_command = matches!(
+        &cli.command,
+        CliCommand::Download(_)
+            | CliCommand::Retry(_)
+            | CliCommand::Watch(_)
+            | CliCommand::Render(_)
+    );
+    let event_handler = if backup_command {
+        progress::create_handler(progress_mode, multi_progress.as_ref())
+    } else {
+        None
+    };
 
-    match &cli.command {
+    let mut summary = match &cli.command {
         CliCommand::Login(args) => LoginCommand::run(args).await,
         //CliCommand::Auth(args) => AuthCommand::run(args),
-        CliCommand::Download(args) => DownloadCommand::run(args).await,
+        CliCommand::Download(args) => DownloadCommand::run(args, event_handler).await,
+        CliCommand::Retry(args) => RetryCommand::run(args, event_handler).await,
+        CliCommand::List(args) => ListCommand::run(args).await,
+        CliCommand::Logout(args) => LogoutCommand::run(args).await,
+        CliCommand::Whoami(args) => WhoamiCommand::run(args).await,
+        CliCommand::Status(args) => StatusCommand::run(args).await,
+        CliCommand::Watch(args) => WatchCommand::run(args, event_handler).await,
+        CliCommand::Config(args) => ConfigCommand::run(args).await,
+        CliCommand::Render(args) => RenderCommand::run(args, event_handler).await,
+        CliCommand::Export(args) => ExportCommand::run(args).await,
+    };
+    summary.finish();
+    if backup_command {
+        summary.print();
     }
+    if let Some(summary_json) = &cli.summary_json {
+        summary.save_json(summary_json);
+    }
+
+    summary.status().into()
 }
 
 //#[cfg(tests)]
@@ -48,7 +119,529 @@ mod tests {
     #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
     async fn download_test() {
         let args = crate::command::download::DownloadArgs::new();
-        crate::command::download::DownloadCommand::run(&args).await;
+        crate::command::download::DownloadCommand::run(&args, None).await;
+    }
+
+    #[test]
+    fn summary_status_test() {
+        use crate::summary::{RunStatus, RunSummary};
+        use kidsnote_sdk::auth::error_types::{AuthError, AuthErrorCode};
+
+        let mut summary = RunSummary::new("download");
+        assert_eq!(summary.status(), RunStatus::Success);
+
+        summary.files_downloaded = 3;
+        summary.files_failed = 1;
+        assert_eq!(summary.status(), RunStatus::PartialFailure);
+        assert!(!summary.is_retryable_failure());
+
+        // 서버 오류로 일부 실패하면 watch 는 잠시 뒤 다시 시도한다.
+        let err = AuthError::ErrorWithCode(AuthErrorCode::from_u16(502));
+        summary.request_error(&err, err.to_string());
+        assert_eq!(summary.status(), RunStatus::PartialFailure);
+        assert!(summary.is_retryable_failure());
+
+        summary.files_downloaded = 0;
+        assert_eq!(summary.status(), RunStatus::TotalFailure);
+
+        summary.auth_fail("login fail".to_string());
+        assert_eq!(summary.status(), RunStatus::AuthFailure);
+    }
+
+    #[test]
+    fn state_failure_test() {
+        use crate::state::{BackupState, FailureClass, FailureTarget};
+        use kidsnote_sdk::{auth::error_types::AuthErrorCode, backup::datatypes::BackupReportRef};
+
+        let output_dir = std::env::temp_dir().join("knbackup_state_failure_test");
+        let output_dir = output_dir.to_str().unwrap();
+        let report = BackupReportRef {
+            child_id: 2,
+            child_name: String::from("홍길동"),
+            report_id: 10,
+            report_date: chrono::Utc::now(),
+        };
+        let error = "status error. status=403";
+
+        let mut state = BackupState::default();
+        let text = FailureTarget::Content {
+            path: String::from("report.txt"),
+        };
+        let card = FailureTarget::Content {
+            path: String::from("report_card.jpg"),
+        };
+        let image = FailureTarget::Image { media_id: 1 };
+        state.add_failure(&report, text.clone(), None, error, None);
+        state.add_failure(&report, text.clone(), None, error, None);
+        // 오류 문구가 아니라 오류 코드로 분류한다.
+        let code = Some(AuthErrorCode::FileError);
+        state.add_failure(&report, image.clone(), None, error, code);
+        state.save(output_dir);
+
+        let mut state = BackupState::from_dir(output_dir);
+        assert_eq!(state.failures.len(), 2);
+        assert_eq!(state.failures[0].attempts, 2);
+        assert_eq!(state.failures[1].error_class, FailureClass::File);
+
+        // 다른 파일을 저장해도 실패 기록은 남는다.
+        state.remove_failure(10, &card);
+        assert_eq!(state.failures.len(), 2);
+        state.remove_failure(10, &text);
+        state.remove_failure(10, &image);
+        assert!(state.failures.is_empty());
+        let _ = std::fs::remove_dir_all(output_dir);
+    }
+
+    #[test]
+    fn state_sync_test() {
+        use crate::state::BackupState;
+        use kidsnote_sdk::backup::event::{BackupEvent, BackupEventHandler};
+
+        let started = BackupEvent::ChildStarted {
+            child_id: 2,
+            child_name: String::from("홍길동"),
+        };
+        let finished = |synced| BackupEvent::ChildFinished {
+            child_id: 2,
+            synced,
+        };
+        let last_synced_at = |state: &BackupState| state.children[&2].last_synced_at;
+
+        // dry run, 날짜 조건이 있는 실행은 마지막 백업 시간을 바꾸지 않는다.
+        let mut state = BackupState::default();
+        state.on_event(&started);
+        state.on_event(&finished(false));
+        assert_eq!(last_synced_at(&state), None);
+        state.on_event(&started);
+        state.on_event(&finished(true));
+        let synced_at = last_synced_at(&state);
+        assert!(synced_at.is_some());
+        state.on_event(&started);
+        state.on_event(&finished(false));
+        assert_eq!(last_synced_at(&state), synced_at);
+
+        // 오류가 있던 실행은 바꾸지 않고, 다음 실행에서는 다시 바꾼다.
+        state.on_event(&started);
+        state.on_event(&BackupEvent::Error {
+            child_id: Some(2),
+            message: String::from("report look up fail."),
+            error_code: None,
+        });
+        state.on_event(&finished(true));
+        assert_eq!(last_synced_at(&state), synced_at);
+        state.on_event(&started);
+        state.on_event(&finished(true));
+        assert!(last_synced_at(&state) > synced_at);
+    }
+
+    #[test]
+    fn state_fallback_test() {
+        use crate::state::BackupState;
+        use kidsnote_sdk::{
+            backup::{
+                datatypes::BackupReportRef,
+                event::{BackupEvent, BackupEventHandler},
+            },
+            resource::datatypes::ImageVariant,
+        };
+
+        let report = BackupReportRef {
+            child_id: 2,
+            child_name: String::from("홍길동"),
+            report_id: 10,
+            report_date: chrono::Utc::now(),
+        };
+        let mut state = BackupState::default();
+        state.on_event(&BackupEvent::MediaDownloaded {
+            report: report.clone(),
+            media_id: 1,
+            path: String::from("large.jpg"),
+            bytes: 1234,
+            variant: ImageVariant::Large,
+            url_refreshed: true,
+        });
+        assert_eq!(state.fallback_sizes().get(&1), Some(&1234));
+
+        // 대신 받은 파일을 건너뛰면 기록을 남기고, 원본이면 지운다.
+        let skipped = |variant| BackupEvent::MediaSkipped {
+            report: report.clone(),
+            media_id: 1,
+            path: String::from("large.jpg"),
+            variant,
+        };
+        state.on_event(&skipped(ImageVariant::Large));
+        assert_eq!(state.fallbacks.len(), 1);
+        state.on_event(&skipped(ImageVariant::Original));
+        assert!(state.fallbacks.is_empty());
+    }
+
+    #[test]
+    fn config_profile_test() {
+        use crate::kidsnote::{KnBackupConfig, TokenStorage};
+
+        let mut config: KnBackupConfig = toml::from_str(
+            r#"
+            [default]
+            user_id = "default_user"
+
+            [profiles.mom]
+            user_id = "mom_user"
+            refresh_token = "mom_token"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.profile(Some("mom")).unwrap().user_id.as_deref(),
+            Some("mom_user")
+        );
+
+        config.set_passphrase(Some("passphrase".to_string()));
+        config
+            .set_profile(
+                Some("dad"),
+                "dad_user".to_string(),
+                "dad_token".to_string(),
+                TokenStorage::Encrypted,
+            )
+            .unwrap();
+        assert_eq!(
+            config.profile_names(),
+            vec![None, Some("dad".to_string()), Some("mom".to_string())]
+        );
+        assert_eq!(
+            config.profile(None).unwrap().user_id.as_deref(),
+            Some("default_user")
+        );
+
+        // 암호화해서 저장, passphrase 가 틀리면 오류
+        let dad = config.profile(Some("dad")).unwrap();
+        assert!(dad.refresh_token.is_none());
+        assert!(dad.encrypted_refresh_token.is_some());
+        let dad = config.login_profile(Some("dad")).unwrap().unwrap();
+        assert_eq!(dad.refresh_token.as_deref(), Some("dad_token"));
+
+        config.set_passphrase(Some("wrong".to_string()));
+        assert!(config.login_profile(Some("dad")).is_err());
+
+        // 평문으로 저장한 계정(이전 버전)은 passphrase 가 없으면 갱신한 token 도 평문으로 저장한다.
+        config.set_passphrase(None);
+        config
+            .set_profile(
+                Some("mom"),
+                "mom_user".to_string(),
+                "mom_token2".to_string(),
+                TokenStorage::Saved,
+            )
+            .unwrap();
+        let mom = config.profile(Some("mom")).unwrap();
+        assert_eq!(mom.refresh_token.as_deref(), Some("mom_token2"));
+        assert!(mom.is_plaintext());
+        // 로그인은 --plaintext-token 일 때만 평문으로 저장한다.
+        assert!(config
+            .set_profile(
+                Some("mom"),
+                "mom_user".to_string(),
+                "mom_token3".to_string(),
+                TokenStorage::Encrypted,
+            )
+            .is_err());
+
+        // passphrase 가 있으면 암호화하고 평문 token 은 지운다.
+        config.set_passphrase(Some("passphrase".to_string()));
+        config
+            .set_profile(
+                Some("mom"),
+                "mom_user".to_string(),
+                "mom_token3".to_string(),
+                TokenStorage::Saved,
+            )
+            .unwrap();
+        let mom = config.profile(Some("mom")).unwrap();
+        assert!(mom.refresh_token.is_none());
+        assert!(mom.encrypted_refresh_token.is_some());
+        assert!(!mom.is_plaintext());
+        let mom = config.login_profile(Some("mom")).unwrap().unwrap();
+        assert_eq!(mom.refresh_token.as_deref(), Some("mom_token3"));
+
+        // --plaintext-token 으로 저장한 계정도 다시 로그인하면 암호화한다.
+        for (storage, plaintext) in [
+            (TokenStorage::Plaintext, true),
+            (TokenStorage::Encrypted, false),
+        ] {
+            config
+                .set_profile(
+                    Some("mom"),
+                    "mom_user".to_string(),
+                    "mom_token4".to_string(),
+                    storage,
+                )
+                .unwrap();
+            let mom = config.profile(Some("mom")).unwrap();
+            assert_eq!(mom.is_plaintext(), plaintext);
+            assert_eq!(mom.refresh_token.is_some(), plaintext);
+        }
+
+        // 새 계정은 passphrase 가 없으면 오류
+        config.set_passphrase(None);
+        assert!(config
+            .set_profile(
+                Some("kid"),
+                "kid_user".to_string(),
+                "kid_token".to_string(),
+                TokenStorage::Encrypted,
+            )
+            .is_err());
+        assert!(config.profile(Some("kid")).is_none());
+
+        // 읽을 수 없는 config 파일은 기본값으로 덮어쓰지 않는다
+        let config_path =
+            std::env::temp_dir().join(format!("knb_config_{}.toml", std::process::id()));
+        std::fs::write(&config_path, "[default\nuser_id = ").unwrap();
+        assert!(KnBackupConfig::from_file(config_path.to_str().unwrap()).is_err());
+        std::fs::remove_file(&config_path).unwrap();
+    }
+
+    #[test]
+    fn config_token_store_test() {
+        use crate::kidsnote::{KnBackupConfig, TokenStorage};
+        use kidsnote_sdk::token_store::datatypes::StoredToken;
+
+        let dir = std::env::temp_dir().join(format!("knb_token_store_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("config.toml").to_str().unwrap().to_string();
+        std::fs::write(&config_path, "[profiles.mom]\nuser_id = \"mom_user\"\n").unwrap();
+
+        let mut config = KnBackupConfig::from_file(&config_path).unwrap();
+        config.set_passphrase(Some("passphrase".to_string()));
+        let mut store = config.into_token_store(
+            Some("mom".to_string()),
+            config_path.clone(),
+            TokenStorage::Saved,
+        );
+
+        // 실행 중에 바뀐 config 를 유지하고 이 계정의 token 만 바꾼다
+        let mut changed = KnBackupConfig::from_file(&config_path).unwrap();
+        changed.download.output_dir = Some("./changed".to_string());
+        changed.save(config_path.clone()).unwrap();
+        let token = StoredToken {
+            user_id: None,
+            refresh_token: "rotated".to_string(),
+        };
+        store.save(&token).unwrap();
+        let saved = KnBackupConfig::from_file(&config_path).unwrap();
+        assert_eq!(saved.download.output_dir.as_deref(), Some("./changed"));
+        assert!(saved
+            .profile(Some("mom"))
+            .unwrap()
+            .encrypted_refresh_token
+            .is_some());
+        assert_eq!(store.load().unwrap().unwrap().refresh_token, "rotated");
+
+        // 저장에 실패하면 오류
+        let blocked = dir.join("blocked");
+        std::fs::write(&blocked, "").unwrap();
+        let config_path = blocked.join("config.toml").to_str().unwrap().to_string();
+        let mut store =
+            KnBackupConfig::default().into_token_store(None, config_path, TokenStorage::Plaintext);
+        assert!(store.save(&token).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn download_config_test() {
+        use crate::command::download::DownloadArgs;
+        use crate::kidsnote::{KnBackupConfig, TokenStorage};
+        use clap::Parser;
+
+        let mut config: KnBackupConfig = toml::from_str(
+            r#"
+            [download]
+            output_dir = "./global"
+            date_start = "2023-01-01"
+
+            [profiles.mom]
+            user_id = "mom_user"
+
+            [profiles.mom.download]
+            output_dir = "./mom"
+            test = true
+            "#,
+        )
+        .unwrap();
+
+        // 명령행 > 계정별 > [download] > 기본값
+        let mut args = DownloadArgs::new();
+        args.date_end = Some("2023-12-31".to_string());
+        args.apply_config(&config.download_config(Some("mom")));
+        assert_eq!(args.output_dir(), "./mom");
+        assert_eq!(args.date_start.as_deref(), Some("2023-01-01"));
+        assert_eq!(args.date_end.as_deref(), Some("2023-12-31"));
+        assert!(args.is_test());
+
+        let mut args = DownloadArgs::new();
+        args.output_dir = Some("./cli".to_string());
+        args.apply_config(&config.download_config(None));
+        assert_eq!(args.output_dir(), "./cli");
+        assert!(!args.is_test());
+
+        // 켜고 끄는 옵션도 명령행 > 환경 변수 > 계정별 > [download]
+        let flags: KnBackupConfig = toml::from_str(
+            r#"
+            [download]
+            report_card = true
+            archive_raw = true
+
+            [profiles.mom.download]
+            test = true
+            report_card = false
+            "#,
+        )
+        .unwrap();
+        std::env::set_var("KNB_ARCHIVE_RAW", "false");
+        let mut args = DownloadArgs::try_parse_from(["download", "--no-test"]).unwrap();
+        args.apply_config(&flags.download_config(Some("mom")));
+        assert!(!args.is_test());
+        assert!(!args.is_report_card());
+        assert!(!args.is_archive_raw());
+        let mut args = DownloadArgs::try_parse_from(["download", "--archive-raw"]).unwrap();
+        args.apply_config(&flags.download_config(None));
+        assert!(!args.is_test());
+        assert!(args.is_report_card());
+        assert!(args.is_archive_raw());
+        std::env::remove_var("KNB_ARCHIVE_RAW");
+
+        // 로그인 정보를 저장해도 계정별 옵션은 유지
+        assert!(config.download.set("test", Some("yes")).is_err());
+        assert!(config.download.set("unknown", Some("1")).is_err());
+        config.set_passphrase(Some("passphrase".to_string()));
+        config
+            .set_profile(
+                Some("mom"),
+                "mom_user".to_string(),
+                "token".to_string(),
+                TokenStorage::Encrypted,
+            )
+            .unwrap();
+        assert_eq!(
+            config.download_config(Some("mom")).output_dir.as_deref(),
+            Some("./mom")
+        );
+    }
+
+    #[test]
+    fn render_theme_config_test() {
+        use crate::kidsnote::KnBackupConfig;
+        use kidsnote_sdk::tool::render_theme::{RenderFormat, RenderTheme};
+
+        let config: KnBackupConfig = toml::from_str(
+            r#"
+            [render]
+            preset = "card"
+            format = "png"
+            fallback_fonts = ["/usr/share/fonts/NotoEmoji-Regular.ttf"]
+
+            [render.body]
+            size = 30
+            "#,
+        )
+        .unwrap();
+        let theme = config.render_theme().unwrap();
+        let card = RenderTheme::preset("card").unwrap();
+        assert_eq!(theme.format, RenderFormat::Png);
+        assert_eq!(theme.body.size, 30.0);
+        assert_eq!(theme.body.color, card.body.color);
+        assert_eq!(theme.header_band, card.header_band);
+        assert_eq!(theme.fallback_fonts.len(), 1);
+
+        let config: KnBackupConfig = toml::from_str("[render]\npreset = \"unknown\"").unwrap();
+        assert!(config.render_theme().is_err());
+        assert_eq!(
+            KnBackupConfig::default().render_theme().unwrap(),
+            RenderTheme::default()
+        );
+    }
+
+    #[test]
+    fn output_lock_test() {
+        use crate::lock::OutputLock;
+
+        let output_dir = std::env::temp_dir().join("knbackup_output_lock_test");
+        let output_dir = output_dir.to_str().unwrap();
+        let _ = std::fs::remove_dir_all(output_dir);
+
+        let lock = OutputLock::acquire(output_dir, "download").unwrap();
+        assert!(OutputLock::acquire(output_dir, "retry").is_err());
+        drop(lock);
+        assert!(!OutputLock::lock_path(output_dir).exists());
+
+        // 비정상 종료로 남은 잠금 파일은 OS 잠금이 풀려 있어서 다시 잠근다.
+        let lock_path = OutputLock::lock_path(output_dir);
+        std::fs::write(
+            &lock_path,
+            r#"{"pid": 2147483000, "host": "localhost", "command": "download", "started_at": "2024-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let lock = OutputLock::acquire(output_dir, "retry").unwrap();
+        let holder = std::fs::read_to_string(&lock_path).unwrap();
+        assert!(holder.contains(&format!("\"pid\": {}", std::process::id())));
+        assert!(OutputLock::acquire(output_dir, "download").is_err());
+        drop(lock);
+        let _ = std::fs::remove_dir_all(output_dir);
+    }
+
+    #[tokio::test]
+    async fn render_export_test() {
+        use crate::command::render::{RenderArgs, RenderCommand};
+        use clap::Parser;
+        use kidsnote_sdk::backup::datatypes::{BackupLayout, BackupReportRecord, BackupSource};
+
+        let output_dir = std::env::temp_dir().join("knbackup_render_export_test");
+        let _ = std::fs::remove_dir_all(&output_dir);
+        let record = BackupReportRecord {
+            child_id: 3,
+            child_name: String::from("홍아기"),
+            child_date_birth: None,
+            center_name: None,
+            saved_at: chrono::Utc::now(),
+            report: serde_json::from_value(serde_json::json!({
+                "id": 12345,
+                "created": "2023-01-10T01:00:00Z",
+                "author_name": "홍길동 선생님",
+                "content": "오늘은 눈이 와서 눈사람을 만들었어요."
+            }))
+            .unwrap(),
+            comments: Vec::new(),
+        };
+        let layout = BackupLayout::new(output_dir.to_str().unwrap());
+        let source = BackupSource::from_record(record.clone());
+        record.save(&layout.record_file(&source)).unwrap();
+
+        let args = RenderArgs::try_parse_from([
+            "render",
+            "--config",
+            output_dir.join("config.toml").to_str().unwrap(),
+            "--output-path",
+            output_dir.to_str().unwrap(),
+        ])
+        .unwrap();
+        let export_dir = output_dir.join("html");
+        let report_page = export_dir.join("홍아기/reports/20230110_12345.html");
+
+        // 웹 페이지를 만든 적이 없으면 만들지 않는다.
+        let summary = RenderCommand::run(&args, None).await;
+        assert!(summary.errors.is_empty(), "{:?}", summary.errors);
+        assert!(layout.text_file(&source).exists());
+        assert!(!export_dir.exists());
+
+        // 웹 페이지 폴더가 있으면 다시 만든다.
+        std::fs::create_dir_all(&export_dir).unwrap();
+        let summary = RenderCommand::run(&args, None).await;
+        assert!(summary.errors.is_empty(), "{:?}", summary.errors);
+        assert_eq!(summary.reports_seen, 1);
+        assert!(export_dir.join("index.html").exists());
+        assert!(report_page.exists());
+        let _ = std::fs::remove_dir_all(&output_dir);
     }
 
     // #[ignore]
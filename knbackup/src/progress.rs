@@ -0,0 +1,263 @@
+use std::{
+    collections::HashMap,
+    io::IsTerminal,
+    time::{Duration, Instant},
+};
+
+use clap::ValueEnum;
+use indicatif::{HumanBytes, MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+
+use kidsnote_sdk::backup::event::{BackupEvent, BackupEventHandler};
+
+/// 진행 상황 표시 방식
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressMode {
+    /// 터미널이면 bar, 아니면 log
+    Auto,
+    Bar,
+    Log,
+    None,
+}
+
+impl ProgressMode {
+    /// auto 를 실제 표시 방식으로 변환
+    pub fn resolve(self) -> ProgressMode {
+        match self {
+            ProgressMode::Auto => {
+                if std::io::stdout().is_terminal() {
+                    ProgressMode::Bar
+                } else {
+                    ProgressMode::Log
+                }
+            }
+            mode => mode,
+        }
+    }
+}
+
+/// 진행 카운트
+#[derive(Debug, Clone, Default)]
+struct ProgressCounter {
+    name: String,
+    pages: u64,
+    reports: u64,
+    total_reports: Option<u64>,
+    queued: u64,
+    downloaded: u64,
+    skipped: u64,
+    failed: u64,
+    bytes: u64,
+}
+
+impl ProgressCounter {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn apply(&mut self, event: &BackupEvent) {
+        match event {
+            BackupEvent::ReportPageFetched {
+                reports,
+                total_reports,
+                ..
+            } => {
+                self.pages += 1;
+                self.reports += reports;
+                if total_reports.is_some() {
+                    self.total_reports = *total_reports;
+                }
+            }
+            BackupEvent::ReportDiscovered { media_count, .. } => {
+                self.queued += media_count;
+            }
+            BackupEvent::MediaDownloaded { bytes, .. } => {
+                self.downloaded += 1;
+                self.bytes += bytes;
+            }
+            BackupEvent::MediaSkipped { .. } => {
+                self.skipped += 1;
+            }
+            BackupEvent::MediaFailed { .. } => {
+                self.failed += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn done(&self) -> u64 {
+        self.downloaded + self.skipped + self.failed
+    }
+
+    fn message(&self) -> String {
+        let reports = match self.total_reports {
+            Some(total_reports) => format!("{}/{}", self.reports, total_reports),
+            None => self.reports.to_string(),
+        };
+        format!(
+            "pages={} reports={} downloaded={} skipped={} failed={} {}",
+            self.pages,
+            reports,
+            self.downloaded,
+            self.skipped,
+            self.failed,
+            HumanBytes(self.bytes)
+        )
+    }
+}
+
+/// 터미널 진행 막대
+pub struct BarProgress {
+    multi: MultiProgress,
+    overall: ProgressBar,
+    overall_counter: ProgressCounter,
+    children: HashMap<u64, (ProgressBar, ProgressCounter)>,
+}
+
+impl BarProgress {
+    pub fn new(multi: MultiProgress) -> Self {
+        let overall = multi.add(ProgressBar::new(0));
+        overall.set_style(
+            ProgressStyle::with_template(
+                "{spinner} [{elapsed_precise}] {prefix} [{bar:30}] {pos}/{len} {msg} ETA {eta}",
+            )
+            .unwrap()
+            .progress_chars("=> "),
+        );
+        overall.set_prefix("전체");
+        overall.enable_steady_tick(Duration::from_millis(200));
+        Self {
+            multi,
+            overall,
+            overall_counter: ProgressCounter::new("전체"),
+            children: HashMap::new(),
+        }
+    }
+
+    fn child_bar(&self, child_name: &str) -> ProgressBar {
+        let bar = self.multi.add(ProgressBar::new(0));
+        bar.set_style(
+            ProgressStyle::with_template("  {prefix} [{bar:30}] {pos}/{len} {msg} ETA {eta}")
+                .unwrap()
+                .progress_chars("=> "),
+        );
+        bar.set_prefix(child_name.to_string());
+        bar
+    }
+}
+
+impl BackupEventHandler for BarProgress {
+    fn on_event(&mut self, event: &BackupEvent) {
+        if let BackupEvent::ChildStarted {
+            child_id,
+            child_name,
+        } = event
+        {
+            if !self.children.contains_key(child_id) {
+                let bar = self.child_bar(child_name);
+                self.children
+                    .insert(*child_id, (bar, ProgressCounter::new(child_name)));
+            }
+        }
+
+        self.overall_counter.apply(event);
+        self.overall.set_length(self.overall_counter.queued);
+        self.overall.set_position(self.overall_counter.done());
+        self.overall.set_message(self.overall_counter.message());
+
+        if let Some(child_id) = event.child_id() {
+            if let Some((bar, counter)) = self.children.get_mut(&child_id) {
+                counter.apply(event);
+                bar.set_length(counter.queued);
+                bar.set_position(counter.done());
+                bar.set_message(counter.message());
+                if let BackupEvent::ChildFinished { .. } = event {
+                    bar.finish();
+                }
+            }
+        }
+
+        if let BackupEvent::Finished = event {
+            self.overall.finish();
+        }
+    }
+}
+
+/// 터미널이 아닌 경우 일정 주기로 로그 출력
+pub struct LogProgress {
+    interval: Duration,
+    last_print: Instant,
+    overall_counter: ProgressCounter,
+    children: HashMap<u64, ProgressCounter>,
+}
+
+impl LogProgress {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_print: Instant::now(),
+            overall_counter: ProgressCounter::new("전체"),
+            children: HashMap::new(),
+        }
+    }
+
+    fn print(&mut self) {
+        self.last_print = Instant::now();
+        for counter in self.children.values() {
+            log::info!(target:"progress", "[{}] {}/{} {}", counter.name, counter.done(), counter.queued, counter.message());
+        }
+        let counter = &self.overall_counter;
+        log::info!(target:"progress", "[{}] {}/{} {}", counter.name, counter.done(), counter.queued, counter.message());
+    }
+}
+
+impl BackupEventHandler for LogProgress {
+    fn on_event(&mut self, event: &BackupEvent) {
+        if let BackupEvent::ChildStarted {
+            child_id,
+            child_name,
+        } = event
+        {
+            self.children
+                .entry(*child_id)
+                .or_insert_with(|| ProgressCounter::new(child_name));
+        }
+
+        self.overall_counter.apply(event);
+        if let Some(counter) = event.child_id().and_then(|f| self.children.get_mut(&f)) {
+            counter.apply(event);
+        }
+
+        match event {
+            BackupEvent::ChildFinished { .. } | BackupEvent::Finished => self.print(),
+            _ => {
+                if self.last_print.elapsed() >= self.interval {
+                    self.print();
+                }
+            }
+        }
+    }
+}
+
+/// 진행 표시 생성
+pub fn create_handler(
+    mode: ProgressMode,
+    multi: Option<&MultiProgress>,
+) -> Option<Box<dyn BackupEventHandler>> {
+    match (mode, multi) {
+        (ProgressMode::Bar, Some(multi)) => Some(Box::new(BarProgress::new(multi.clone()))),
+        (ProgressMode::Log, _) => Some(Box::new(LogProgress::new(Duration::from_secs(30)))),
+        _ => None,
+    }
+}
+
+/// 진행 막대를 그릴 MultiProgress 생성
+pub fn create_multi_progress(mode: ProgressMode) -> Option<MultiProgress> {
+    if mode == ProgressMode::Bar {
+        Some(MultiProgress::with_draw_target(ProgressDrawTarget::stdout()))
+    } else {
+        None
+    }
+}
@@ -0,0 +1,445 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use kidsnote_sdk::{
+    auth::error_types::AuthErrorCode,
+    backup::{
+        datatypes::BackupReportRef,
+        event::{BackupEvent, BackupEventHandler},
+    },
+    resource::datatypes::ImageVariant,
+};
+use serde::{Deserialize, Serialize};
+
+/// 출력 폴더 안의 상태 파일 경로
+const STATE_DIR: &str = ".knbackup";
+const STATE_FILE: &str = "state.json";
+const WATCH_FILE: &str = "watch.json";
+
+/// 상태 파일 저장
+/// - 저장 중 종료되어도 기존 파일이 깨지지 않도록 임시 파일로 저장 후 교체
+fn write_json<T: Serialize>(path: &Path, value: &T) {
+    if let Some(parent_dir) = path.parent() {
+        if !parent_dir.exists() {
+            if let Err(err) = fs::create_dir_all(parent_dir) {
+                log::error!(target:"state", "state file dir create fail. {}", err);
+                return;
+            }
+        }
+    }
+
+    let json_string = match serde_json::to_string_pretty(value) {
+        Ok(json_string) => json_string,
+        Err(err) => {
+            log::error!(target:"state", "state serialize fail. {}", err);
+            return;
+        }
+    };
+
+    let temp_path = path.with_extension("json.tmp");
+    if let Err(err) = fs::write(&temp_path, json_string) {
+        log::error!(target:"state", "state file save fail. {}", err);
+        return;
+    }
+    if let Err(err) = fs::rename(&temp_path, path) {
+        log::error!(target:"state", "state file rename fail. {}", err);
+    }
+}
+
+/// 실패 원인 분류
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClass {
+    Network,
+    Status,
+    File,
+    Parse,
+    Render,
+    NotFound,
+    Unknown,
+}
+
+impl FailureClass {
+    /// 오류 코드로 분류한다.
+    pub fn from_code(code: Option<AuthErrorCode>) -> Self {
+        match code {
+            Some(AuthErrorCode::NetworkError) => FailureClass::Network,
+            Some(AuthErrorCode::FileError) => FailureClass::File,
+            Some(AuthErrorCode::ParseError) => FailureClass::Parse,
+            Some(
+                AuthErrorCode::BadRequest
+                | AuthErrorCode::Unauthorized
+                | AuthErrorCode::Forbidden
+                | AuthErrorCode::NotFound
+                | AuthErrorCode::Gone
+                | AuthErrorCode::ServerError
+                | AuthErrorCode::UnknownError,
+            ) => FailureClass::Status,
+            _ => FailureClass::Unknown,
+        }
+    }
+}
+
+/// 실패 항목 종류
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureKind {
+    /// 알림장 텍스트(.txt, .jpg) 저장
+    Content,
+    /// 첨부 이미지 다운로드
+    Image,
+}
+
+/// 실패한 결과물
+/// - 알림장 텍스트는 파일(.json, .txt, .jpg, 카드)마다, 이미지는 이미지 id 마다 따로 기록한다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailureTarget {
+    Content { path: String },
+    Image { media_id: u64 },
+}
+
+impl FailureTarget {
+    pub fn kind(&self) -> FailureKind {
+        match self {
+            FailureTarget::Content { .. } => FailureKind::Content,
+            FailureTarget::Image { .. } => FailureKind::Image,
+        }
+    }
+}
+
+/// 다시 받아야 하는 항목
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FailedItem {
+    pub kind: FailureKind,
+    pub child_id: u64,
+    pub child_name: String,
+    pub report_id: u64,
+    pub report_date: DateTime<Utc>,
+    pub media_id: Option<u64>,
+    /// 저장하지 못한 파일 (알림장 텍스트). 이전 상태 파일은 None
+    #[serde(default)]
+    pub path: Option<String>,
+    pub url: Option<String>,
+    pub error_class: FailureClass,
+    pub error: String,
+    pub attempts: u32,
+    pub first_failed_at: DateTime<Utc>,
+    pub last_failed_at: DateTime<Utc>,
+}
+
+impl FailedItem {
+    /// 파일을 기록하지 않은 이전 알림장 텍스트 실패는 같은 알림장의 어떤 파일과도 같다고 본다.
+    fn is_same(&self, report_id: u64, target: &FailureTarget) -> bool {
+        if self.report_id != report_id {
+            return false;
+        }
+        match target {
+            FailureTarget::Content { path } => {
+                self.kind == FailureKind::Content && self.path.as_ref().is_none_or(|f| f == path)
+            }
+            FailureTarget::Image { media_id } => {
+                self.kind == FailureKind::Image && self.media_id == Some(*media_id)
+            }
+        }
+    }
+}
+
+/// 원본 대신 다른 크기의 이미지를 받은 항목
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FallbackItem {
+    pub child_id: u64,
+    pub report_id: u64,
+    pub media_id: u64,
+    pub variant: ImageVariant,
+    pub path: String,
+    /// 받은 파일 크기. 이전 상태 파일은 0
+    #[serde(default)]
+    pub bytes: u64,
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// 자녀별 백업 기록
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ChildSyncState {
+    pub child_name: String,
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// 빠짐없이 조회하고 오류 없이 끝난 마지막 백업 (dry run, 날짜 조건이 있는 실행, retry 제외)
+    pub last_synced_at: Option<DateTime<Utc>>,
+}
+
+/// 백업 상태 파일
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BackupState {
+    #[serde(default)]
+    pub failures: Vec<FailedItem>,
+    #[serde(default)]
+    pub fallbacks: Vec<FallbackItem>,
+    #[serde(default)]
+    pub children: BTreeMap<u64, ChildSyncState>,
+    /// 이번 실행에서 조회, 저장, 다운로드 오류가 있었던 자녀
+    #[serde(skip)]
+    failed_children: HashSet<u64>,
+}
+
+impl BackupState {
+    pub fn state_path(output_dir: &str) -> PathBuf {
+        Path::new(output_dir).join(STATE_DIR).join(STATE_FILE)
+    }
+
+    pub fn from_dir(output_dir: &str) -> BackupState {
+        let state_path = Self::state_path(output_dir);
+        match fs::read_to_string(&state_path) {
+            Ok(json_string) => match serde_json::from_str(&json_string) {
+                Ok(state) => state,
+                Err(err) => {
+                    log::error!(target:"state", "state file parse fail. path={}, {}", state_path.display(), err);
+                    BackupState::default()
+                }
+            },
+            Err(_) => BackupState::default(),
+        }
+    }
+
+    pub fn save(&self, output_dir: &str) {
+        write_json(&Self::state_path(output_dir), self);
+    }
+
+    /// 실패 항목 추가. 이미 있으면 시도 횟수를 올린다.
+    pub fn add_failure(
+        &mut self,
+        report: &BackupReportRef,
+        target: FailureTarget,
+        url: Option<String>,
+        error: &str,
+        error_code: Option<AuthErrorCode>,
+    ) {
+        let now = Utc::now();
+        let error_class = match target {
+            FailureTarget::Content { .. } => FailureClass::Render,
+            FailureTarget::Image { .. } => FailureClass::from_code(error_code),
+        };
+        if let Some(item) = self
+            .failures
+            .iter_mut()
+            .find(|f| f.is_same(report.report_id, &target))
+        {
+            if let FailureTarget::Content { path } = &target {
+                item.path = Some(path.clone());
+            }
+            item.url = url.or(item.url.take());
+            item.error_class = error_class;
+            item.error = error.to_string();
+            item.attempts += 1;
+            item.last_failed_at = now;
+        } else {
+            let (media_id, path) = match &target {
+                FailureTarget::Content { path } => (None, Some(path.clone())),
+                FailureTarget::Image { media_id } => (Some(*media_id), None),
+            };
+            self.failures.push(FailedItem {
+                kind: target.kind(),
+                child_id: report.child_id,
+                child_name: report.child_name.clone(),
+                report_id: report.report_id,
+                report_date: report.report_date,
+                media_id,
+                path,
+                url,
+                error_class,
+                error: error.to_string(),
+                attempts: 1,
+                first_failed_at: now,
+                last_failed_at: now,
+            });
+        }
+    }
+
+    /// 찾을 수 없는 항목으로 표시
+    pub fn mark_not_found(&mut self, report_id: u64) {
+        let now = Utc::now();
+        for item in self
+            .failures
+            .iter_mut()
+            .filter(|f| f.report_id == report_id)
+        {
+            item.error_class = FailureClass::NotFound;
+            item.error = String::from("report not found");
+            item.attempts += 1;
+            item.last_failed_at = now;
+        }
+    }
+
+    /// 원본 대신 받은 이미지 기록
+    pub fn add_fallback(
+        &mut self,
+        report: &BackupReportRef,
+        media_id: u64,
+        variant: ImageVariant,
+        path: &str,
+        bytes: u64,
+    ) {
+        self.remove_fallback(report.report_id, media_id);
+        self.fallbacks.push(FallbackItem {
+            child_id: report.child_id,
+            report_id: report.report_id,
+            media_id,
+            variant,
+            path: path.to_string(),
+            bytes,
+            recorded_at: Utc::now(),
+        });
+    }
+
+    /// 원본 대신 받아둔 이미지 (이미지 id, 파일 크기)
+    /// - 크기를 기록하지 않은 항목은 지금 파일 크기를 쓴다.
+    pub fn fallback_sizes(&self) -> HashMap<u64, u64> {
+        self.fallbacks
+            .iter()
+            .filter_map(|f| match f.bytes {
+                0 => fs::metadata(&f.path).ok().map(|m| (f.media_id, m.len())),
+                bytes => Some((f.media_id, bytes)),
+            })
+            .collect()
+    }
+
+    /// 원본을 다시 받은 경우 기록 제거
+    pub fn remove_fallback(&mut self, report_id: u64, media_id: u64) {
+        self.fallbacks
+            .retain(|f| !(f.report_id == report_id && f.media_id == media_id));
+    }
+
+    /// 성공한 항목 제거
+    pub fn remove_failure(&mut self, report_id: u64, target: &FailureTarget) {
+        self.failures.retain(|f| !f.is_same(report_id, target));
+    }
+}
+
+impl BackupEventHandler for BackupState {
+    fn on_event(&mut self, event: &BackupEvent) {
+        match event {
+            BackupEvent::ChildStarted {
+                child_id,
+                child_name,
+            } => {
+                // watch 처럼 같은 상태로 여러 번 실행하면 이전 실행의 오류는 지운다.
+                self.failed_children.remove(child_id);
+                let child = self.children.entry(*child_id).or_default();
+                child.child_name = child_name.clone();
+                child.last_run_at = Some(Utc::now());
+            }
+            BackupEvent::Error {
+                child_id: Some(child_id),
+                ..
+            } => {
+                self.failed_children.insert(*child_id);
+            }
+            BackupEvent::ChildFinished {
+                child_id,
+                synced: true,
+            } if !self.failed_children.contains(child_id) => {
+                if let Some(child) = self.children.get_mut(child_id) {
+                    child.last_synced_at = Some(Utc::now());
+                }
+            }
+            BackupEvent::ReportNotFound { report_id, .. } => {
+                self.mark_not_found(*report_id);
+            }
+            BackupEvent::ContentSaved { report, path } => {
+                let target = FailureTarget::Content { path: path.clone() };
+                self.remove_failure(report.report_id, &target);
+            }
+            BackupEvent::ContentFailed {
+                report,
+                path,
+                error,
+            } => {
+                self.failed_children.insert(report.child_id);
+                let target = FailureTarget::Content { path: path.clone() };
+                self.add_failure(report, target, None, error, None);
+            }
+            BackupEvent::MediaDownloaded {
+                report,
+                media_id,
+                path,
+                bytes,
+                variant,
+                ..
+            } => {
+                let target = FailureTarget::Image {
+                    media_id: *media_id,
+                };
+                self.remove_failure(report.report_id, &target);
+                if *variant == ImageVariant::Original {
+                    self.remove_fallback(report.report_id, *media_id);
+                } else {
+                    self.add_fallback(report, *media_id, *variant, path, *bytes);
+                }
+            }
+            BackupEvent::MediaSkipped {
+                report,
+                media_id,
+                variant,
+                ..
+            } => {
+                let target = FailureTarget::Image {
+                    media_id: *media_id,
+                };
+                self.remove_failure(report.report_id, &target);
+                if *variant == ImageVariant::Original {
+                    self.remove_fallback(report.report_id, *media_id);
+                }
+            }
+            BackupEvent::MediaFailed {
+                report,
+                media_id,
+                url,
+                error,
+                error_code,
+                ..
+            } => {
+                self.failed_children.insert(report.child_id);
+                let target = FailureTarget::Image {
+                    media_id: *media_id,
+                };
+                self.add_failure(report, target, Some(url.clone()), error, *error_code);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// watch 실행 상태 (.knbackup/watch.json)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WatchStatus {
+    pub pid: u32,
+    pub started_at: Option<DateTime<Utc>>,
+    /// running, waiting, stopped
+    pub state: String,
+    pub runs: u64,
+    pub last_run_started_at: Option<DateTime<Utc>>,
+    pub last_run_finished_at: Option<DateTime<Utc>>,
+    pub last_status: Option<String>,
+    pub last_error: Option<String>,
+    /// 연속으로 실패한 횟수. 0 이 아니면 재시도 간격을 늘린다.
+    pub consecutive_failures: u32,
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl WatchStatus {
+    pub fn watch_path(output_dir: &str) -> PathBuf {
+        Path::new(output_dir).join(STATE_DIR).join(WATCH_FILE)
+    }
+
+    pub fn from_dir(output_dir: &str) -> Option<WatchStatus> {
+        let json_string = fs::read_to_string(Self::watch_path(output_dir)).ok()?;
+        serde_json::from_str(&json_string).ok()
+    }
+
+    pub fn save(&self, output_dir: &str) {
+        write_json(&Self::watch_path(output_dir), self);
+    }
+}
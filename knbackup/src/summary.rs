@@ -0,0 +1,254 @@
+use std::{fs, path::PathBuf, process::ExitCode};
+
+use chrono::{DateTime, Utc};
+use kidsnote_sdk::{
+    auth::error_types::{AuthError, AuthErrorCode},
+    backup::event::{BackupEvent, BackupEventHandler},
+    resource::datatypes::ImageVariant,
+};
+use serde::Serialize;
+
+/// 종료 코드
+/// - 0 : 성공
+/// - 1 : 전체 실패 (성공한 작업이 없음)
+/// - 2 : 잘못된 매개변수 (clap)
+/// - 3 : 인증 실패
+/// - 4 : 일부 실패
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum RunStatus {
+    Success = 0,
+    TotalFailure = 1,
+    AuthFailure = 3,
+    PartialFailure = 4,
+}
+
+impl RunStatus {
+    pub const fn as_u8(&self) -> u8 {
+        *self as u8
+    }
+}
+
+impl From<RunStatus> for ExitCode {
+    fn from(status: RunStatus) -> Self {
+        ExitCode::from(status.as_u8())
+    }
+}
+
+/// 명령 실행 결과 요약
+#[derive(Debug, Clone, Serialize)]
+pub struct RunSummary {
+    pub command: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub auth_failed: bool,
+    pub reports_seen: u64,
+    pub reports_failed: u64,
+    pub files_rendered: u64,
+    pub files_downloaded: u64,
+    pub files_skipped: u64,
+    pub files_failed: u64,
+    /// 원본 대신 large 이미지를 받은 수
+    pub files_fallback: u64,
+    pub bytes_downloaded: u64,
+    /// 서버 오류(5xx)로 실패한 수
+    pub server_errors: u64,
+    pub errors: Vec<String>,
+}
+
+impl RunSummary {
+    pub fn new(command: &str) -> Self {
+        Self {
+            command: command.to_string(),
+            started_at: Utc::now(),
+            finished_at: None,
+            auth_failed: false,
+            reports_seen: 0,
+            reports_failed: 0,
+            files_rendered: 0,
+            files_downloaded: 0,
+            files_skipped: 0,
+            files_failed: 0,
+            files_fallback: 0,
+            bytes_downloaded: 0,
+            server_errors: 0,
+            errors: Vec::new(),
+        }
+    }
+
+    /// 인증 실패
+    pub fn auth_fail(&mut self, message: String) {
+        self.auth_failed = true;
+        self.errors.push(message);
+    }
+
+    /// 일반 오류
+    pub fn error(&mut self, message: String) {
+        self.errors.push(message);
+    }
+
+    /// 요청 오류. 서버 오류는 따로 센다.
+    pub fn request_error(&mut self, err: &AuthError, message: String) {
+        self.count_server_error(err.code());
+        self.error(message);
+    }
+
+    fn count_server_error(&mut self, error_code: Option<AuthErrorCode>) {
+        if error_code == Some(AuthErrorCode::ServerError) {
+            self.server_errors += 1;
+        }
+    }
+
+    /// 다른 실행 결과를 합친다. 인증 실패는 둘 다 실패한 경우만 유지한다.
+    pub fn merge(&mut self, other: RunSummary) {
+        self.auth_failed = self.auth_failed && other.auth_failed;
+        self.reports_seen += other.reports_seen;
+        self.reports_failed += other.reports_failed;
+        self.files_rendered += other.files_rendered;
+        self.files_downloaded += other.files_downloaded;
+        self.files_skipped += other.files_skipped;
+        self.files_failed += other.files_failed;
+        self.files_fallback += other.files_fallback;
+        self.bytes_downloaded += other.bytes_downloaded;
+        self.server_errors += other.server_errors;
+        self.errors.extend(other.errors);
+    }
+
+    pub fn finish(&mut self) {
+        self.finished_at = Some(Utc::now());
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.auth_failed
+            || self.reports_failed > 0
+            || self.files_failed > 0
+            || !self.errors.is_empty()
+    }
+
+    pub fn is_succeeded_any(&self) -> bool {
+        self.reports_seen > 0
+            || self.files_rendered > 0
+            || self.files_downloaded > 0
+            || self.files_skipped > 0
+    }
+
+    /// 전체 실패이거나 서버 오류로 일부 실패함. 잠시 뒤 다시 시도하면 될 수 있다.
+    pub fn is_retryable_failure(&self) -> bool {
+        match self.status() {
+            RunStatus::TotalFailure => true,
+            RunStatus::PartialFailure => self.server_errors > 0,
+            _ => false,
+        }
+    }
+
+    pub fn status(&self) -> RunStatus {
+        if self.auth_failed {
+            RunStatus::AuthFailure
+        } else if !self.is_failed() {
+            RunStatus::Success
+        } else if !self.is_succeeded_any() {
+            RunStatus::TotalFailure
+        } else {
+            RunStatus::PartialFailure
+        }
+    }
+
+    pub fn print(&self) {
+        let elapsed = self
+            .finished_at
+            .unwrap_or_else(Utc::now)
+            .signed_duration_since(self.started_at);
+        log::info!(target:"summary", "[{}] status={:?}, elapsed={}s", self.command, self.status(), elapsed.num_seconds());
+        log::info!(target:"summary", "reports seen={}, failed={}", self.reports_seen, self.reports_failed);
+        log::info!(target:"summary", "files rendered={}, downloaded={}, skipped={}, failed={}, fallback={}, bytes={}", self.files_rendered, self.files_downloaded, self.files_skipped, self.files_failed, self.files_fallback, self.bytes_downloaded);
+        for error in &self.errors {
+            log::error!(target:"summary", "{}", error);
+        }
+    }
+
+    pub fn save_json(&self, save_path: &str) {
+        let save_path = PathBuf::from(save_path);
+        if let Some(parent_dir) = save_path.parent() {
+            if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+                if let Err(err) = fs::create_dir_all(parent_dir) {
+                    log::error!(target:"summary", "summary file dir create fail. {}", err);
+                }
+            }
+        }
+        match serde_json::to_string_pretty(self) {
+            Ok(json_string) => {
+                if let Err(err) = fs::write(&save_path, json_string) {
+                    log::error!(target:"summary", "summary file save fail. {}", err);
+                } else {
+                    log::info!(target:"summary", "summary file save. path={}", save_path.display());
+                }
+            }
+            Err(err) => {
+                log::error!(target:"summary", "summary serialize fail. {}", err);
+            }
+        }
+    }
+}
+
+impl BackupEventHandler for RunSummary {
+    fn on_event(&mut self, event: &BackupEvent) {
+        match event {
+            BackupEvent::ReportDiscovered { .. } => {
+                self.reports_seen += 1;
+            }
+            BackupEvent::ReportNotFound {
+                child_id,
+                report_id,
+            } => {
+                self.files_failed += 1;
+                self.error(format!("[{}][{}] report not found.", child_id, report_id));
+            }
+            BackupEvent::ContentSaved { .. } => {
+                self.files_rendered += 1;
+            }
+            BackupEvent::ContentFailed { report, error, .. } => {
+                self.files_failed += 1;
+                self.error(format!(
+                    "[{}][{}] content save fail. {}",
+                    report.child_name, report.report_id, error
+                ));
+            }
+            BackupEvent::MediaDownloaded { bytes, variant, .. } => {
+                self.files_downloaded += 1;
+                self.bytes_downloaded += bytes;
+                if *variant != ImageVariant::Original {
+                    self.files_fallback += 1;
+                }
+            }
+            BackupEvent::MediaSkipped { .. } => {
+                self.files_skipped += 1;
+            }
+            BackupEvent::MediaFailed {
+                report,
+                media_id,
+                error,
+                error_code,
+                ..
+            } => {
+                self.files_failed += 1;
+                self.count_server_error(*error_code);
+                self.error(format!(
+                    "[{}][{}][{}] image download fail. {}",
+                    report.child_name, report.report_id, media_id, error
+                ));
+            }
+            BackupEvent::Error {
+                child_id,
+                message,
+                error_code,
+            } => {
+                if child_id.is_some() {
+                    self.reports_failed += 1;
+                }
+                self.count_server_error(*error_code);
+                self.error(message.clone());
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use crate::{auth::error_types::AuthError, child::datatypes::GetReportsParam};
+
+/// API 응답 원문 보관
+/// - 서버에서 받은 그대로 gzip 으로 압축해서 {dir}/{endpoint}/{key}.json.gz 에 저장한다.
+/// - 같은 endpoint, key 로 다시 받으면 덮어쓴다.
+#[derive(Debug, Clone)]
+pub struct ResponseArchive {
+    dir: PathBuf,
+}
+
+impl ResponseArchive {
+    /// 내정보 (/v1/me/info/)
+    pub const ME_INFO: &'static str = "me_info";
+
+    pub fn new<P: Into<PathBuf>>(dir: P) -> ResponseArchive {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 자녀의 알림장 목록 (/v1_2/children/{id}/reports/)
+    pub fn reports_endpoint(child_id: u64) -> String {
+        format!("children/{}/reports", child_id)
+    }
+
+    /// 알림장의 댓글 목록 (/v1/reports/{id}/comments/). 페이지 이름은 page, 첫 페이지는 1
+    pub fn comments_endpoint(report_id: u64) -> String {
+        format!("reports/{}/comments", report_id)
+    }
+
+    /// 알림장 목록 페이지 이름
+    /// - {date_start}_{date_end}_{page}. 날짜가 없으면 all, 첫 페이지는 1
+    pub fn reports_key(param: &GetReportsParam) -> String {
+        let date_start = param.date_start.as_deref().unwrap_or("all");
+        let date_end = param.date_end.as_deref().unwrap_or("all");
+        let page = param.page.as_deref().unwrap_or("1");
+        format!("{}_{}_{}", date_start, date_end, page)
+    }
+
+    /// 파일 이름에 쓸 수 없는 글자는 _ 로 바꾼다.
+    /// - 바꾸거나 120 자보다 길어서 자른 key 는 다른 key 와 겹치지 않도록 원래 key 의 crc32 를 붙인다.
+    pub fn file_path(&self, endpoint: &str, key: &str) -> PathBuf {
+        let sanitized: String = key
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' => c,
+                _ => '_',
+            })
+            .collect();
+        let file_name = if sanitized == key && key.len() <= 120 {
+            sanitized
+        } else {
+            let prefix: String = sanitized.chars().take(111).collect();
+            format!("{}_{:08x}", prefix, crc32fast::hash(key.as_bytes()))
+        };
+        self.dir
+            .join(endpoint)
+            .join(format!("{}.json.gz", file_name))
+    }
+
+    pub fn save(&self, endpoint: &str, key: &str, body: &[u8]) -> Result<PathBuf, AuthError> {
+        let file_path = self.file_path(endpoint, key);
+        if let Some(parent_dir) = file_path.parent() {
+            fs::create_dir_all(parent_dir).map_err(|err| {
+                AuthError::GeneralErrorStr(format!(
+                    "archive dir create fail. path={}, {}",
+                    parent_dir.display(),
+                    err
+                ))
+            })?;
+        }
+
+        // 중간에 멈춰도 깨진 파일이 남지 않도록 임시 파일에 쓰고 바꾼다.
+        let temp_path = file_path.with_extension("gz.tmp");
+        let save_error = |err: std::io::Error| {
+            AuthError::GeneralErrorStr(format!(
+                "archive save fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        };
+        let file = fs::File::create(&temp_path).map_err(save_error)?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder.write_all(body).map_err(save_error)?;
+        encoder.finish().map_err(save_error)?;
+        fs::rename(&temp_path, &file_path).map_err(save_error)?;
+        Ok(file_path)
+    }
+
+    /// 저장에 실패해도 요청은 계속한다.
+    pub fn record(&self, endpoint: &str, key: &str, body: &[u8]) {
+        match self.save(endpoint, key, body) {
+            Ok(file_path) => {
+                log::debug!(target: "archive", "response saved. path={}", file_path.display())
+            }
+            Err(err) => log::warn!(target: "archive", "{}", err),
+        }
+    }
+
+    pub fn load(&self, endpoint: &str, key: &str) -> Result<Vec<u8>, AuthError> {
+        Self::read_file(&self.file_path(endpoint, key))
+    }
+
+    /// 압축을 푼 응답 원문
+    pub fn read_file(file_path: &Path) -> Result<Vec<u8>, AuthError> {
+        let file = fs::File::open(file_path).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "archive read fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        })?;
+        let mut body = Vec::new();
+        GzDecoder::new(file).read_to_end(&mut body).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "archive read fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        })?;
+        Ok(body)
+    }
+
+    /// endpoint 의 저장된 파일 (이름 순서)
+    pub fn files(&self, endpoint: &str) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(self.dir.join(endpoint)) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|f| f.ok())
+            .map(|f| f.path())
+            .filter(|f| f.to_string_lossy().ends_with(".json.gz"))
+            .collect();
+        files.sort();
+        files
+    }
+}
@@ -1,17 +1,33 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::schema::{extra_fields, null_default, ExtraFields, UnknownFields};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct KidsnoteAccessToken {
     pub r#type: String,
     pub token: String,
     pub expires_in: i32,
+    /// 만료 시각. 알 수 없으면 None
+    #[serde(default)]
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct OAuthTokenResponse {
     pub token_type: String,
     pub access_token: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub scope: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub expires_in: i32,
     pub refresh_token: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl UnknownFields for OAuthTokenResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+    }
 }
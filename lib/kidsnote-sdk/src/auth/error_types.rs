@@ -15,13 +15,57 @@ pub enum AuthError {
     GeneralErrorStr(String),
 }
 
+impl AuthError {
+    pub fn code(&self) -> Option<AuthErrorCode> {
+        match self {
+            AuthError::ErrorWithCode(code)
+            | AuthError::ErrorWithCodeStr(code, _)
+            | AuthError::ErrorWithCodeString(code, _) => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// 서명된 리소스 주소가 만료되었을 때
+    pub fn is_expired_url(&self) -> bool {
+        matches!(
+            self.code(),
+            Some(AuthErrorCode::Unauthorized | AuthErrorCode::Forbidden | AuthErrorCode::Gone)
+        )
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.code(), Some(AuthErrorCode::NotFound | AuthErrorCode::Gone))
+    }
+
+    /// 로그인 정보(아이디, 비밀번호, refresh token)가 틀렸을 때
+    pub fn is_invalid_credentials(&self) -> bool {
+        matches!(
+            self.code(),
+            Some(AuthErrorCode::BadRequest | AuthErrorCode::Unauthorized)
+        )
+    }
+
+    pub fn is_server_error(&self) -> bool {
+        matches!(self.code(), Some(AuthErrorCode::ServerError))
+    }
+}
+
 #[repr(u16)]
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Copy, TryFromPrimitive, Error)]
 pub enum AuthErrorCode {
     None = 0x0000,
     Nothingtodo = 1,
+    BadRequest = 400,
     Unauthorized = 401,
+    Forbidden = 403,
     NotFound = 404,
+    Gone = 410,
+    /// 서버에 연결할 수 없거나 응답을 받지 못함
+    NetworkError = 0xfffb,
+    /// 파일, 폴더를 읽거나 쓸 수 없음
+    FileError = 0xfffc,
+    /// 응답을 읽을 수 없음
+    ParseError = 0xfffd,
     ServerError = 0xfffe,
     UnknownError = 0xffff,
 }
@@ -30,9 +74,11 @@ impl AuthErrorCode {
     pub const fn as_u16(&self) -> u16 {
         *self as u16
     }
+    /// HTTP 상태 코드. 5xx 는 ServerError
     pub fn from_u16(num: u16) -> Self {
         match Self::try_from_primitive(num) {
             Ok(v) => v,
+            Err(_) if (500..600).contains(&num) => AuthErrorCode::ServerError,
             Err(_) => AuthErrorCode::UnknownError,
         }
     }
@@ -45,8 +91,20 @@ impl AuthErrorCode {
             AuthErrorCode::Nothingtodo => "Nothing to do",
             // 찾을 수 없습니다.
             AuthErrorCode::NotFound => "Not Found",
+            // 잘못된 요청 (로그인 정보가 틀림)
+            AuthErrorCode::BadRequest => "Bad Request",
             // 미인증
             AuthErrorCode::Unauthorized => "Unauthorized",
+            // 권한 없음 (만료된 주소)
+            AuthErrorCode::Forbidden => "Forbidden",
+            // 더 이상 없음
+            AuthErrorCode::Gone => "Gone",
+            // 서버에 연결할 수 없습니다.
+            AuthErrorCode::NetworkError => "Network Error",
+            // 파일을 읽거나 쓸 수 없습니다.
+            AuthErrorCode::FileError => "File Error",
+            // 응답을 읽을 수 없습니다.
+            AuthErrorCode::ParseError => "Parse Error",
             // 서버오류가 발생했습니다.
             AuthErrorCode::ServerError => "Server Processing Error Occured",
             // 알수없는 오류가 발생했습니다.
@@ -6,27 +6,49 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use crate::options::KidsnoteOptions;
+use crate::{
+    options::KidsnoteOptions,
+    schema::decode_response,
+    token_store::{datatypes::StoredToken, TokenStore},
+};
 
-use self::{datatypes::OAuthTokenResponse, error_types::AuthError};
+use self::{
+    datatypes::OAuthTokenResponse,
+    error_types::{AuthError, AuthErrorCode},
+};
 
+/// 인증 sdk
+/// - 복제해도 세션과 token store 를 함께 쓴다. (child, user sdk 에서 세션 갱신)
+#[derive(Clone)]
 pub struct KidsnoteAuthSdk {
     options: Arc<Mutex<KidsnoteOptions>>,
+    token_store: Arc<Mutex<Option<Box<dyn TokenStore>>>>,
 }
 
 impl KidsnoteAuthSdk {
     pub fn new(options: Arc<Mutex<KidsnoteOptions>>) -> KidsnoteAuthSdk {
-        Self { options }
+        Self {
+            options,
+            token_store: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn set_token_store(&mut self, token_store: Box<dyn TokenStore>) {
+        *self.token_store.lock().unwrap() = Some(token_store);
     }
 
     // oauth token
     pub async fn oauth_token(
-        &mut self,
+        &self,
         params: HashMap<&str, &str>,
     ) -> Result<OAuthTokenResponse, AuthError> {
-        let mut options = self.options.lock().unwrap();
-
-        let url = format!("{}/o/token/", options.get_host_ref());
+        let (url, client_id) = {
+            let options = self.options.lock().unwrap();
+            (
+                format!("{}/o/token/", options.get_host_ref()),
+                options.get_client_id(),
+            )
+        };
 
         let body = serde_urlencoded::to_string(&params)
             .map_err(|_e| AuthError::GeneralError("serde_urlencoded"))?;
@@ -36,45 +58,154 @@ impl KidsnoteAuthSdk {
             .post(url)
             .header("Content-Type", "application/x-www-form-urlencoded")
             //.header("User-Agent", "kidsnote/4.41.1 (Build/11382) (iPhone; iOS 16.2; Scale/3.00)")
-            .header(
-                "Authorization",
-                format!("Basic {}", options.get_client_id_ref()),
-            )
+            .header("Authorization", format!("Basic {}", client_id))
             .body(body)
             .send()
             .await;
 
-        match response {
+        let result = match response {
             Ok(response) => {
                 if response.status().is_success() {
-                    match response.json::<OAuthTokenResponse>().await {
-                        Ok(result) => {
-                            options.set_session_by_oauth(result.clone());
-                            Ok(result)
-                        }
+                    match response.bytes().await {
+                        Ok(body) => decode_response::<OAuthTokenResponse>("oauth_token", &body),
                         Err(e) => {
-                            options.remove_session();
                             //log::error!("update_world_multilingual error: {}", e);
                             Err(AuthError::GeneralErrorStr(format!("parse error. {:?}", e)))
                         }
                     }
                 } else {
-                    options.remove_session();
                     //log::error!("update_world_multilingual error: {}", response.status().as_u16());
-                    Err(AuthError::GeneralErrorStr(format!(
-                        "status error. status={:?}",
-                        response.status()
-                    )))
+                    let status = response.status();
+                    Err(AuthError::ErrorWithCodeString(
+                        AuthErrorCode::from_u16(status.as_u16()),
+                        format!("status error. status={:?}", status),
+                    ))
                 }
             }
             Err(e) => {
-                options.remove_session();
                 //log::error!("update_world_multilingual error: {}", e);
                 Err(AuthError::GeneralErrorStr(format!(
                     "unknown error. {:?}",
                     e
                 )))
             }
+        };
+
+        let stored_token = {
+            let mut options = self.options.lock().unwrap();
+            match &result {
+                Ok(result) => {
+                    options.set_session_by_oauth(result.clone());
+                    Some(StoredToken {
+                        user_id: options.get_user_id(),
+                        refresh_token: result.refresh_token.clone(),
+                    })
+                }
+                Err(_) => {
+                    options.remove_session();
+                    None
+                }
+            }
+        };
+
+        // 토큰이 바뀌었으면 이전 토큰은 더 쓸 수 없으므로 바로 저장
+        // - 저장에 실패하면 다음 실행에서 로그인할 수 없으므로 오류로 돌려준다.
+        let mut token_store = self.token_store.lock().unwrap();
+        if let (Some(stored_token), Some(token_store)) = (stored_token, token_store.as_mut()) {
+            if let Err(err) = token_store.save(&stored_token) {
+                log::error!(target:"auth", "token store save fail. {}", err);
+                return Err(AuthError::GeneralErrorStr(format!(
+                    "token store save fail. {}",
+                    err
+                )));
+            }
+        }
+        result
+    }
+
+    /// 토큰 폐기 (서버에서 더 이상 사용할 수 없게 한다)
+    pub async fn revoke_token(&mut self, token: &str) -> Result<(), AuthError> {
+        let (url, client_id) = {
+            let options = self.options.lock().unwrap();
+            (
+                format!("{}/o/revoke_token/", options.get_host_ref()),
+                options.get_client_id(),
+            )
+        };
+
+        let mut params = HashMap::new();
+        params.insert("token", token);
+        params.insert("token_type_hint", "refresh_token");
+        let body = serde_urlencoded::to_string(&params)
+            .map_err(|_e| AuthError::GeneralError("serde_urlencoded"))?;
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .header("Authorization", format!("Basic {}", client_id))
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| AuthError::GeneralErrorStr(format!("unknown error. {:?}", e)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(AuthError::ErrorWithCodeString(
+                AuthErrorCode::from_u16(status.as_u16()),
+                format!("status error. status={:?}", status),
+            ))
+        }
+    }
+
+    /// 저장소의 로그인 정보로 refresh. 저장된 정보가 없으면 None
+    pub async fn restore_session(&self) -> Result<Option<OAuthTokenResponse>, AuthError> {
+        let stored_token = match self.token_store.lock().unwrap().as_mut() {
+            Some(token_store) => token_store.load()?,
+            None => None,
+        };
+        let Some(stored_token) = stored_token else {
+            return Ok(None);
+        };
+        if let Some(user_id) = stored_token.user_id {
+            self.options.lock().unwrap().set_user_id(user_id);
+        }
+        self.refresh_token(&stored_token.refresh_token)
+            .await
+            .map(Some)
+    }
+
+    /// 세션 갱신. 지금 refresh token 이 없으면 저장소의 로그인 정보를 사용한다.
+    /// - 새 refresh token 은 저장소에 바로 저장된다.
+    pub async fn refresh_session(&self) -> Result<OAuthTokenResponse, AuthError> {
+        let refresh_token = self.options.lock().unwrap().get_refresh_token();
+        match refresh_token {
+            Some(refresh_token) => self.refresh_token(&refresh_token).await,
+            None => self
+                .restore_session()
+                .await?
+                .ok_or(AuthError::ErrorWithCode(AuthErrorCode::Unauthorized)),
+        }
+    }
+
+    /// access token 이 없거나 만료되었으면 세션 갱신
+    pub async fn ensure_session(&self) -> Result<(), AuthError> {
+        let expired = self.options.lock().unwrap().is_access_token_expired();
+        if expired {
+            log::info!(target:"auth", "access token expired. refresh session.");
+            self.refresh_session().await?;
+        }
+        Ok(())
+    }
+
+    /// 로그아웃. 세션과 저장소의 로그인 정보를 지운다.
+    pub fn clear_session(&mut self) -> Result<(), AuthError> {
+        self.options.lock().unwrap().remove_session();
+        match self.token_store.lock().unwrap().as_mut() {
+            Some(token_store) => token_store.clear(),
+            None => Ok(()),
         }
     }
 
@@ -84,6 +215,11 @@ impl KidsnoteAuthSdk {
         username: &str,
         password: &str,
     ) -> Result<OAuthTokenResponse, AuthError> {
+        self.options
+            .lock()
+            .unwrap()
+            .set_user_id(username.to_string());
+
         let mut data = HashMap::new();
         data.insert("grant_type", "password");
         data.insert("username", username);
@@ -95,7 +231,7 @@ impl KidsnoteAuthSdk {
 
     /// refresh token
     pub async fn refresh_token(
-        &mut self,
+        &self,
         refresh_token: &str,
     ) -> Result<OAuthTokenResponse, AuthError> {
         let mut data = HashMap::new();
@@ -0,0 +1,512 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use filetime::FileTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    archive::ResponseArchive,
+    auth::error_types::AuthError,
+    backup::BackupJob,
+    child::datatypes::{
+        ChildReportDataResponse, ChildReportResponse, GetReportsParam, ReportCommentDataResponse,
+        ReportCommentResponse,
+    },
+    resource::datatypes::{ReportMediaRef, ResourceImageResponse},
+    schema::decode_response,
+    tool::{image_tool::RenderReport, render_theme::RenderFormat},
+    user::datatypes::{MeInfoChildrenResponse, MeInfoResponse},
+};
+
+/// 다운로드 대상 알림장
+#[derive(Debug, Clone)]
+pub struct BackupSource {
+    pub source_type: String,
+    pub source_id: u64,
+    pub report_date: DateTime<Utc>,
+    pub report_content: Option<String>,
+    pub author_name: String,
+    pub center_name: Option<String>,
+    pub class_id: u64,
+    pub class_name: String,
+    pub child_id: u64,
+    pub child_name: String,
+    /// 자녀 생일 (yyyy-mm-dd)
+    pub child_date_birth: Option<String>,
+    pub weather: Option<String>,
+    /// 부모가 읽은 시간
+    pub read_at: Option<String>,
+    pub attached_images: Vec<ResourceImageResponse>,
+    /// 조회한 알림장 그대로 (.json). 텍스트를 저장하지 않는 대상은 None
+    pub raw_report: Option<ChildReportDataResponse>,
+    /// 댓글. 받지 않았으면 비어 있다.
+    pub comments: Vec<ReportCommentDataResponse>,
+}
+
+impl BackupSource {
+    /// 알림장 조회 결과를 다운로드 대상으로 변환
+    pub fn from_report(
+        report: ChildReportDataResponse,
+        child: &MeInfoChildrenResponse,
+        center_map: &HashMap<u64, String>,
+    ) -> Self {
+        let center_name = report.center.and_then(|f| center_map.get(&f).cloned());
+        Self::from_parts(
+            report,
+            child.id,
+            child.name.clone(),
+            Some(child.date_birth.clone()),
+            center_name,
+        )
+    }
+
+    /// 저장해둔 원문 파일을 대상으로 변환
+    pub fn from_record(record: BackupReportRecord) -> Self {
+        let mut source = Self::from_parts(
+            record.report,
+            record.child_id,
+            record.child_name,
+            record.child_date_birth,
+            record.center_name,
+        );
+        source.comments = record.comments;
+        source
+    }
+
+    fn from_parts(
+        report: ChildReportDataResponse,
+        child_id: u64,
+        child_name: String,
+        child_date_birth: Option<String>,
+        center_name: Option<String>,
+    ) -> Self {
+        let raw_report = report.clone();
+        Self {
+            source_type: String::from("알림장"),
+            source_id: report.id,
+            report_date: report.created,
+            report_content: report.content,
+            author_name: report.author_name,
+            center_name: center_name.or(Some(String::from(""))),
+            class_id: report.cls,
+            class_name: report.class_name,
+            child_id,
+            child_name,
+            child_date_birth: child_date_birth.filter(|f| !f.is_empty()),
+            weather: report.weather,
+            read_at: Some(report.read_by_parent.date_read).filter(|f| !f.is_empty()),
+            attached_images: report.attached_images,
+            raw_report: Some(raw_report),
+            comments: Vec::new(),
+        }
+    }
+
+    /// 받아야 할 댓글이 있는지
+    pub fn has_comments(&self) -> bool {
+        self.raw_report.as_ref().is_some_and(|f| f.num_comments > 0)
+    }
+
+    /// 이미지, 텍스트 파일로 저장할 알림장
+    pub fn render_report(&self, title: &str, contents: Vec<String>) -> RenderReport {
+        RenderReport {
+            title: title.to_string(),
+            center_name: self.center_name.clone(),
+            author_name: self.author_name.clone(),
+            contents,
+            report_id: Some(self.source_id),
+            report_date: self.report_date,
+            weather: self.weather.clone(),
+            class_name: Some(self.class_name.clone()),
+            child_date_birth: self.child_date_birth.clone(),
+            read_at: self.read_at.clone(),
+        }
+    }
+
+    /// 원문 파일 내용
+    pub fn record(&self) -> Option<BackupReportRecord> {
+        Some(BackupReportRecord {
+            child_id: self.child_id,
+            child_name: self.child_name.clone(),
+            child_date_birth: self.child_date_birth.clone(),
+            center_name: self.center_name.clone().filter(|f| !f.is_empty()),
+            saved_at: Utc::now(),
+            report: self.raw_report.clone()?,
+            comments: self.comments.clone(),
+        })
+    }
+
+    pub fn report_ref(&self) -> BackupReportRef {
+        BackupReportRef {
+            child_id: self.child_id,
+            child_name: self.child_name.clone(),
+            report_id: self.source_id,
+            report_date: self.report_date,
+        }
+    }
+
+    pub fn media_ref(&self) -> ReportMediaRef {
+        ReportMediaRef {
+            child_id: self.child_id,
+            report_id: self.source_id,
+            report_date: self.report_date,
+        }
+    }
+}
+
+/// 알림장 원문 파일 (.json)
+/// - report.content 는 서버에서 받은 그대로이고, .txt 와 이미지는 정리한 내용으로 만든다.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupReportRecord {
+    pub child_id: u64,
+    pub child_name: String,
+    pub child_date_birth: Option<String>,
+    pub center_name: Option<String>,
+    pub saved_at: DateTime<Utc>,
+    pub report: ChildReportDataResponse,
+    /// 댓글 (조회 결과 그대로)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<ReportCommentDataResponse>,
+}
+
+impl BackupReportRecord {
+    /// 파일 날짜는 알림장 날짜로 맞춘다.
+    pub fn save(&self, file_path: &Path) -> Result<(), AuthError> {
+        if let Some(parent_dir) = file_path.parent() {
+            fs::create_dir_all(parent_dir).map_err(|err| {
+                AuthError::GeneralErrorStr(format!(
+                    "record dir create fail. path={}, {}",
+                    file_path.display(),
+                    err
+                ))
+            })?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| AuthError::GeneralErrorStr(format!("record serialize fail. {}", err)))?;
+        fs::write(file_path, json).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "record save fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        })?;
+
+        let file_time = FileTime::from_unix_time(self.report.created.timestamp(), 0);
+        if let Err(err) = filetime::set_file_times(file_path, file_time, file_time) {
+            log::warn!(target: "backup", "set_file_times fail. path={}, {}", file_path.display(), err);
+        }
+        Ok(())
+    }
+
+    pub fn from_file(file_path: &Path) -> Result<Self, AuthError> {
+        let json = fs::read_to_string(file_path).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "record read fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        })?;
+        serde_json::from_str(&json).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "record parse fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        })
+    }
+}
+
+/// 받아둔 알림장
+#[derive(Debug, Clone, Default)]
+pub struct LocalSources {
+    pub sources: Vec<BackupSource>,
+    /// 읽을 수 없는 파일
+    pub errors: Vec<String>,
+}
+
+/// 이벤트에 담기는 알림장 정보
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupReportRef {
+    pub child_id: u64,
+    pub child_name: String,
+    pub report_id: u64,
+    pub report_date: DateTime<Utc>,
+}
+
+/// 백업 대상
+#[derive(Debug, Clone)]
+pub enum BackupTarget {
+    /// 자녀의 알림장 전체 (filter 적용)
+    Child(u64),
+    /// 알림장 한 건
+    Report {
+        child_id: u64,
+        report_id: u64,
+        report_date: DateTime<Utc>,
+        /// None 이면 첨부 이미지 전체
+        media_ids: Option<Vec<u64>>,
+        /// false 면 텍스트(.txt, .jpg, .json)를 만들지 않는다.
+        content: bool,
+    },
+}
+
+/// 알림장 조회 조건
+#[derive(Debug, Clone, Default)]
+pub struct BackupFilter {
+    pub date_start: Option<String>,
+    pub date_end: Option<String>,
+}
+
+impl BackupFilter {
+    /// 날짜 하나만 있으면 그 날짜만 조회한다.
+    pub fn to_param(&self) -> GetReportsParam {
+        let mut param = GetReportsParam::new();
+        let (date_start, date_end) = match (&self.date_start, &self.date_end) {
+            (Some(date_start), Some(date_end)) => (date_start, date_end),
+            (Some(date), None) | (None, Some(date)) => (date, date),
+            (None, None) => return param,
+        };
+        param.date_start = Some(date_start.clone());
+        param.date_end = Some(date_end.clone());
+        param.tz = Some("Asia/Seoul".to_string());
+        param
+    }
+}
+
+/// 저장할 결과물
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackupSink {
+    /// 알림장 텍스트 (.txt)
+    Text,
+    /// 알림장 텍스트를 그린 이미지 (.jpg, 테마의 형식)
+    RenderedImage,
+    /// 첨부 이미지
+    Media,
+    /// 알림장 원문과 조회 결과 (.json)
+    Record,
+    /// 알림장 내용과 첨부 이미지를 함께 그린 카드 (_card.jpg). all 에 포함되지 않는다.
+    ReportCard,
+}
+
+impl BackupSink {
+    pub fn all() -> Vec<BackupSink> {
+        vec![
+            BackupSink::Text,
+            BackupSink::RenderedImage,
+            BackupSink::Media,
+            BackupSink::Record,
+        ]
+    }
+}
+
+/// 저장 경로
+/// - {output}/키즈노트 {자녀}/알림장/{yyyy-mm}/{yyyymmdd}_{자녀}_알림장_{id}.txt
+#[derive(Debug, Clone)]
+pub struct BackupLayout {
+    pub output_dir: String,
+}
+
+impl BackupLayout {
+    pub fn new(output_dir: &str) -> BackupLayout {
+        Self {
+            output_dir: output_dir.to_string(),
+        }
+    }
+
+    pub fn report_dir(&self, source: &BackupSource) -> PathBuf {
+        let mut output_base_path = PathBuf::from(&self.output_dir);
+        output_base_path.push(format!("키즈노트 {}", source.child_name));
+        output_base_path.push(&source.source_type);
+        output_base_path.push(source.report_date.format("%Y-%m").to_string());
+        output_base_path
+    }
+
+    fn report_file(&self, source: &BackupSource, extension: &str) -> PathBuf {
+        self.report_dir(source).join(format!(
+            "{}_{}_{}_{}.{}",
+            source.report_date.format("%Y%m%d"),
+            source.child_name,
+            source.source_type,
+            source.source_id,
+            extension
+        ))
+    }
+
+    pub fn text_file(&self, source: &BackupSource) -> PathBuf {
+        self.report_file(source, "txt")
+    }
+
+    pub fn record_file(&self, source: &BackupSource) -> PathBuf {
+        self.report_file(source, "json")
+    }
+
+    pub fn image_file(&self, source: &BackupSource, format: RenderFormat) -> PathBuf {
+        self.report_file(source, format.extension())
+    }
+
+    /// 사진 카드. 여러 장이면 1부터 쪽 번호를 붙인다.
+    pub fn card_file(
+        &self,
+        source: &BackupSource,
+        format: RenderFormat,
+        page: Option<usize>,
+    ) -> PathBuf {
+        let suffix = match page {
+            Some(page) => format!("card_{}", page),
+            None => String::from("card"),
+        };
+        self.report_dir(source).join(format!(
+            "{}_{}_{}_{}_{}.{}",
+            source.report_date.format("%Y%m%d"),
+            source.child_name,
+            source.source_type,
+            source.source_id,
+            suffix,
+            format.extension()
+        ))
+    }
+
+    /// 저장된 원문 파일 (.json) 전체
+    /// - {output}/키즈노트 {자녀}/{종류}/{yyyy-mm}/*.json
+    pub fn record_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for child_dir in Self::sub_dirs(Path::new(&self.output_dir)) {
+            let is_child_dir = child_dir
+                .file_name()
+                .and_then(|f| f.to_str())
+                .is_some_and(|f| f.starts_with("키즈노트 "));
+            if !is_child_dir {
+                continue;
+            }
+            for month_dir in Self::sub_dirs(&child_dir)
+                .iter()
+                .flat_map(|f| Self::sub_dirs(f))
+            {
+                let Ok(entries) = fs::read_dir(&month_dir) else {
+                    continue;
+                };
+                files.extend(
+                    entries
+                        .filter_map(|f| f.ok())
+                        .map(|f| f.path())
+                        .filter(|f| f.extension().is_some_and(|f| f == "json")),
+                );
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn sub_dirs(dir: &Path) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        entries
+            .filter_map(|f| f.ok())
+            .map(|f| f.path())
+            .filter(|f| f.is_dir())
+            .collect()
+    }
+
+    /// 서버에 요청하지 않고 받아둔 알림장을 읽는다. (알림장 날짜 순서)
+    /// - 원문 파일(.json)을 먼저 읽고, 원문 파일이 없는 알림장은 응답 보관 파일(archive)에서 찾는다.
+    /// - 읽을 수 없는 파일은 errors 에 남기고 건너뛴다.
+    pub fn local_sources(&self, archive: Option<&ResponseArchive>) -> LocalSources {
+        let mut local = LocalSources::default();
+        let mut report_ids = HashSet::new();
+        for record_file in self.record_files() {
+            match BackupReportRecord::from_file(&record_file) {
+                Ok(record) => {
+                    if report_ids.insert(record.report.id) {
+                        local.sources.push(BackupSource::from_record(record));
+                    }
+                }
+                Err(err) => local.errors.push(err.to_string()),
+            }
+        }
+
+        if let Some(archive) = archive {
+            let me_info = match archive.load(ResponseArchive::ME_INFO, ResponseArchive::ME_INFO) {
+                Ok(body) => decode_response::<MeInfoResponse>(ResponseArchive::ME_INFO, &body)
+                    .map_err(|err| local.errors.push(err.to_string()))
+                    .ok(),
+                Err(_) => None,
+            };
+            for child in me_info.map(|f| f.children).unwrap_or_default() {
+                let center_map = BackupJob::center_map(&child);
+                for page_file in archive.files(&ResponseArchive::reports_endpoint(child.id)) {
+                    let page = ResponseArchive::read_file(&page_file).and_then(|f| {
+                        decode_response::<ChildReportResponse>("reports", &f).map_err(|err| {
+                            AuthError::GeneralErrorStr(format!(
+                                "archive parse fail. path={}, {}",
+                                page_file.display(),
+                                err
+                            ))
+                        })
+                    });
+                    match page {
+                        Ok(page) => {
+                            for report in page.results {
+                                if report_ids.insert(report.id) {
+                                    let mut source =
+                                        BackupSource::from_report(report, &child, &center_map);
+                                    source.comments =
+                                        Self::archive_comments(archive, source.source_id)
+                                            .map_err(|err| local.errors.push(err.to_string()))
+                                            .unwrap_or_default();
+                                    local.sources.push(source);
+                                }
+                            }
+                        }
+                        Err(err) => local.errors.push(err.to_string()),
+                    }
+                }
+            }
+        }
+        local
+            .sources
+            .sort_by_key(|f| (f.child_id, f.report_date, f.source_id));
+        local
+    }
+
+    /// 응답 보관 파일에서 찾은 알림장 댓글
+    fn archive_comments(
+        archive: &ResponseArchive,
+        report_id: u64,
+    ) -> Result<Vec<ReportCommentDataResponse>, AuthError> {
+        let mut comments = Vec::new();
+        for page_file in archive.files(&ResponseArchive::comments_endpoint(report_id)) {
+            let page = ResponseArchive::read_file(&page_file)?;
+            let page =
+                decode_response::<ReportCommentResponse>("comments", &page).map_err(|err| {
+                    AuthError::GeneralErrorStr(format!(
+                        "archive parse fail. path={}, {}",
+                        page_file.display(),
+                        err
+                    ))
+                })?;
+            comments.extend(page.results);
+        }
+        comments.sort_by_key(|f| (f.created, f.id));
+        Ok(comments)
+    }
+
+    pub fn media_file(&self, source: &BackupSource, image: &ResourceImageResponse) -> PathBuf {
+        let extension = Path::new(&image.original_file_name)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("png");
+        self.report_dir(source).join(format!(
+            "{}_{}_{}_{}_{}.{}",
+            source.report_date.format("%Y%m%d"),
+            source.child_name,
+            source.source_type,
+            source.source_id,
+            image.id,
+            extension
+        ))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{auth::error_types::AuthErrorCode, resource::datatypes::ImageVariant};
+
+use super::datatypes::BackupReportRef;
+
+/// 백업 진행 이벤트
+#[derive(Debug, Clone)]
+pub enum BackupEvent {
+    /// 자녀 알림장 조회 시작
+    ChildStarted { child_id: u64, child_name: String },
+    /// 알림장 한 페이지 조회
+    ReportPageFetched {
+        child_id: u64,
+        reports: u64,
+        total_reports: Option<u64>,
+    },
+    /// 다운로드할 알림장 발견
+    ReportDiscovered {
+        report: BackupReportRef,
+        media_count: u64,
+    },
+    /// 다시 조회했지만 찾을 수 없는 알림장
+    ReportNotFound { child_id: u64, report_id: u64 },
+    /// 알림장 텍스트 저장 (.txt, .jpg)
+    ContentSaved {
+        report: BackupReportRef,
+        path: String,
+    },
+    ContentFailed {
+        report: BackupReportRef,
+        path: String,
+        error: String,
+    },
+    MediaDownloaded {
+        report: BackupReportRef,
+        media_id: u64,
+        path: String,
+        bytes: u64,
+        variant: ImageVariant,
+        url_refreshed: bool,
+    },
+    /// 이미 받은 파일
+    MediaSkipped {
+        report: BackupReportRef,
+        media_id: u64,
+        path: String,
+        /// 받아둔 파일이 원본 대신 받은 이미지면 Large
+        variant: ImageVariant,
+    },
+    MediaFailed {
+        report: BackupReportRef,
+        media_id: u64,
+        url: String,
+        path: String,
+        error: String,
+        /// 오류 종류. 코드가 없는 오류는 None
+        error_code: Option<AuthErrorCode>,
+    },
+    /// 알림장 조회 오류 등 계속 진행 가능한 오류
+    Error {
+        child_id: Option<u64>,
+        message: String,
+        /// 오류 종류. 코드가 없는 오류는 None
+        error_code: Option<AuthErrorCode>,
+    },
+    /// 자녀 알림장 조회 끝
+    ChildFinished {
+        child_id: u64,
+        /// 자녀의 알림장을 빠짐없이 조회한 경우.
+        /// dry run, 날짜 조건이 있는 조회(증분 조회 제외), 알림장 한 건 대상이면 false
+        synced: bool,
+    },
+    /// 전체 작업 끝
+    Finished,
+}
+
+impl BackupEvent {
+    pub fn child_id(&self) -> Option<u64> {
+        match self {
+            BackupEvent::ChildStarted { child_id, .. }
+            | BackupEvent::ReportPageFetched { child_id, .. }
+            | BackupEvent::ReportNotFound { child_id, .. }
+            | BackupEvent::ChildFinished { child_id, .. } => Some(*child_id),
+            BackupEvent::ReportDiscovered { report, .. }
+            | BackupEvent::ContentSaved { report, .. }
+            | BackupEvent::ContentFailed { report, .. }
+            | BackupEvent::MediaDownloaded { report, .. }
+            | BackupEvent::MediaSkipped { report, .. }
+            | BackupEvent::MediaFailed { report, .. } => Some(report.child_id),
+            BackupEvent::Error { child_id, .. } => *child_id,
+            BackupEvent::Finished => None,
+        }
+    }
+}
+
+/// 백업 이벤트 수신
+pub trait BackupEventHandler {
+    fn on_event(&mut self, event: &BackupEvent);
+}
+
+impl<F> BackupEventHandler for F
+where
+    F: FnMut(&BackupEvent),
+{
+    fn on_event(&mut self, event: &BackupEvent) {
+        self(event)
+    }
+}
@@ -0,0 +1,763 @@
+pub mod datatypes;
+pub mod event;
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    time::Duration,
+};
+
+use chrono::{TimeZone, Utc};
+use filetime::FileTime;
+
+use crate::{
+    auth::error_types::AuthError,
+    child::KidsnoteChildSdk,
+    resource::datatypes::ImageVariant,
+    tool::{image_tool::ImageTool, render_theme::RenderTheme},
+    user::datatypes::MeInfoChildrenResponse,
+    KidsnoteSdk,
+};
+
+use self::{
+    datatypes::{
+        BackupFilter, BackupLayout, BackupReportRef, BackupSink, BackupSource, BackupTarget,
+    },
+    event::{BackupEvent, BackupEventHandler},
+};
+
+/// 알림장 백업 작업
+pub struct BackupJob {
+    targets: Vec<BackupTarget>,
+    filter: BackupFilter,
+    layout: BackupLayout,
+    sinks: Vec<BackupSink>,
+    children: Option<Vec<MeInfoChildrenResponse>>,
+    skip_reports: HashSet<u64>,
+    fallbacks: HashMap<u64, u64>,
+    theme: RenderTheme,
+    dry_run: bool,
+    incremental: bool,
+}
+
+pub struct BackupJobBuilder {
+    targets: Vec<BackupTarget>,
+    filter: BackupFilter,
+    layout: BackupLayout,
+    sinks: Option<Vec<BackupSink>>,
+    children: Option<Vec<MeInfoChildrenResponse>>,
+    skip_reports: HashSet<u64>,
+    fallbacks: HashMap<u64, u64>,
+    theme: RenderTheme,
+    dry_run: bool,
+    incremental: bool,
+}
+
+impl BackupJobBuilder {
+    pub fn new() -> BackupJobBuilder {
+        Self {
+            targets: Vec::new(),
+            filter: BackupFilter::default(),
+            layout: BackupLayout::new("./output"),
+            sinks: None,
+            children: None,
+            skip_reports: HashSet::new(),
+            fallbacks: HashMap::new(),
+            theme: RenderTheme::default(),
+            dry_run: false,
+            incremental: false,
+        }
+    }
+
+    /// 대상을 지정하지 않으면 모든 자녀의 알림장을 받는다.
+    pub fn target(mut self, target: BackupTarget) -> Self {
+        self.targets.push(target);
+        self
+    }
+
+    pub fn targets(mut self, targets: Vec<BackupTarget>) -> Self {
+        self.targets.extend(targets);
+        self
+    }
+
+    pub fn filter(mut self, filter: BackupFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn date_start(mut self, date_start: Option<String>) -> Self {
+        self.filter.date_start = date_start;
+        self
+    }
+
+    pub fn date_end(mut self, date_end: Option<String>) -> Self {
+        self.filter.date_end = date_end;
+        self
+    }
+
+    pub fn layout(mut self, layout: BackupLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn output_dir(mut self, output_dir: &str) -> Self {
+        self.layout.output_dir = output_dir.to_string();
+        self
+    }
+
+    /// 여러 결과물을 한 번에 지정
+    pub fn sinks(mut self, sinks: Vec<BackupSink>) -> Self {
+        for sink in sinks {
+            self = self.sink(sink);
+        }
+        self
+    }
+
+    /// 지정하지 않으면 전체 저장
+    pub fn sink(mut self, sink: BackupSink) -> Self {
+        let sinks = self.sinks.get_or_insert_with(Vec::new);
+        if !sinks.contains(&sink) {
+            sinks.push(sink);
+        }
+        self
+    }
+
+    /// 이미 조회한 자녀 정보. 없으면 실행할 때 내정보를 조회한다.
+    pub fn children(mut self, children: Vec<MeInfoChildrenResponse>) -> Self {
+        self.children = Some(children);
+        self
+    }
+
+    /// 이미 받은 알림장 id. 여러 계정의 같은 자녀를 받을 때 중복을 건너뛴다.
+    pub fn skip_reports(mut self, report_ids: HashSet<u64>) -> Self {
+        self.skip_reports.extend(report_ids);
+        self
+    }
+
+    /// 원본 대신 받아둔 large 이미지 (이미지 id, 파일 크기). 같은 크기의 파일이 있으면 다시 받지 않는다.
+    pub fn fallbacks(mut self, fallbacks: HashMap<u64, u64>) -> Self {
+        self.fallbacks.extend(fallbacks);
+        self
+    }
+
+    /// 알림장 이미지 모양
+    pub fn theme(mut self, theme: RenderTheme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// 파일을 저장하지 않고 조회만 한다.
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// 마지막 백업 이전 날짜부터 조회하는 증분 백업. 날짜 조건이 있어도 빠짐없이 조회한 것으로 본다.
+    pub fn incremental(mut self, incremental: bool) -> Self {
+        self.incremental = incremental;
+        self
+    }
+
+    pub fn build(self) -> BackupJob {
+        BackupJob {
+            targets: self.targets,
+            filter: self.filter,
+            layout: self.layout,
+            sinks: self.sinks.unwrap_or_else(BackupSink::all),
+            children: self.children,
+            skip_reports: self.skip_reports,
+            fallbacks: self.fallbacks,
+            theme: self.theme,
+            dry_run: self.dry_run,
+            incremental: self.incremental,
+        }
+    }
+}
+
+impl Default for BackupJobBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackupJob {
+    pub fn builder() -> BackupJobBuilder {
+        BackupJobBuilder::new()
+    }
+
+    pub fn layout(&self) -> &BackupLayout {
+        &self.layout
+    }
+
+    /// 백업 실행
+    /// - 내정보 조회에 실패한 경우에만 Err 를 반환하고, 나머지 오류는 이벤트로 전달한다.
+    pub async fn run(
+        &mut self,
+        sdk: &mut KidsnoteSdk,
+        handler: &mut dyn BackupEventHandler,
+    ) -> Result<(), AuthError> {
+        let children = match self.children.take() {
+            Some(children) => children,
+            None => sdk.user().get_myinfo().await?.children,
+        };
+
+        let targets = if self.targets.is_empty() {
+            children.iter().map(|f| BackupTarget::Child(f.id)).collect()
+        } else {
+            self.targets.clone()
+        };
+
+        // 자녀 전체 대상
+        for target in &targets {
+            let BackupTarget::Child(child_id) = target else {
+                continue;
+            };
+            let Some(child) = children.iter().find(|f| f.id == *child_id) else {
+                handler.on_event(&BackupEvent::Error {
+                    child_id: Some(*child_id),
+                    message: format!("[{}] child not found.", child_id),
+                    error_code: None,
+                });
+                continue;
+            };
+            handler.on_event(&BackupEvent::ChildStarted {
+                child_id: child.id,
+                child_name: child.name.clone(),
+            });
+            let result = self.step_child_report_download(sdk, handler, child).await;
+            if let Err(err) = &result {
+                log::error!(target:"backup","step_child_report_download error. {}", err);
+                handler.on_event(&BackupEvent::Error {
+                    child_id: Some(child.id),
+                    message: format!("[{}] report look up fail. {}", child.name, err),
+                    error_code: err.code(),
+                });
+            }
+            handler.on_event(&BackupEvent::ChildFinished {
+                child_id: child.id,
+                synced: result.is_ok() && self.is_full_sync(),
+            });
+        }
+
+        // 알림장 한 건 대상은 자녀, 날짜별로 묶어서 조회
+        let mut report_groups: BTreeMap<(u64, String), Vec<&BackupTarget>> = BTreeMap::new();
+        for target in &targets {
+            if let BackupTarget::Report {
+                child_id,
+                report_date,
+                ..
+            } = target
+            {
+                report_groups
+                    .entry((*child_id, KidsnoteChildSdk::report_local_date(*report_date)))
+                    .or_default()
+                    .push(target);
+            }
+        }
+        for ((child_id, date), report_targets) in report_groups {
+            self.step_report_targets_download(
+                sdk,
+                handler,
+                &children,
+                child_id,
+                &date,
+                report_targets,
+            )
+            .await;
+        }
+
+        handler.on_event(&BackupEvent::Finished);
+        Ok(())
+    }
+
+    /// 자녀의 알림장을 빠짐없이 조회하는 작업인지
+    fn is_full_sync(&self) -> bool {
+        let unfiltered = self.filter.date_start.is_none() && self.filter.date_end.is_none();
+        !self.dry_run && (unfiltered || self.incremental)
+    }
+
+    /// 자녀의 원 id - 원 이름
+    pub fn center_map(child: &MeInfoChildrenResponse) -> HashMap<u64, String> {
+        let mut center_map = HashMap::new();
+        for enroll in &child.enrollment {
+            center_map
+                .entry(enroll.center_id)
+                .or_insert(enroll.center_name.clone());
+        }
+        center_map
+    }
+
+    /// 알림장
+    async fn step_child_report_download(
+        &self,
+        sdk: &mut KidsnoteSdk,
+        handler: &mut dyn BackupEventHandler,
+        child: &MeInfoChildrenResponse,
+    ) -> Result<i32, AuthError> {
+        log::info!(target:"backup","Child:[{}]:Start", child.id);
+        let center_map = Self::center_map(child);
+
+        let mut result = 0;
+
+        // cls로는 필터링 되는데 center로는 필터가 안된다.
+
+        // 날짜 필터링
+        let mut report_options = self.filter.to_param();
+
+        let mut loop_count = 0;
+        loop {
+            loop_count += 1;
+
+            log::debug!(target: "backup", "[Child][{}][report] look up. page={:?}, ds={:?}, de={:?}", child.name, report_options.page, report_options.date_start, report_options.date_end);
+            match sdk
+                .child()
+                .get_reports(child.id, Some(report_options.clone()))
+                .await
+            {
+                Ok(report_result) => {
+                    report_options.page = report_result.next.clone();
+                    handler.on_event(&BackupEvent::ReportPageFetched {
+                        child_id: child.id,
+                        reports: report_result.results.len() as u64,
+                        total_reports: u64::try_from(report_result.count).ok(),
+                    });
+
+                    if !report_result.results.is_empty() {
+                        let mut download_sources = Vec::new();
+                        for report in report_result.results {
+                            download_sources.push(BackupSource::from_report(
+                                report,
+                                child,
+                                &center_map,
+                            ));
+                            result += 1;
+                        }
+                        self.step_report_source_download(sdk, handler, download_sources)
+                            .await;
+                    } else {
+                        report_options.page = None;
+                    }
+                }
+                Err(err) => {
+                    log::error!(target: "backup", "[Child][{}][report] look up error. {}", child.name, err);
+                    return Err(err);
+                }
+            }
+
+            if report_options.page.is_none() {
+                break;
+            }
+
+            tokio::time::sleep(Duration::from_secs(1)).await;
+
+            if loop_count > 10000 {
+                log::error!("The loop count is excessive. loop={}", loop_count);
+                break;
+            }
+        }
+
+        log::info!("[child][{}][report] End.", child.id);
+
+        Ok(result)
+    }
+
+    /// 지정한 알림장만 다시 조회해서 받는다.
+    async fn step_report_targets_download(
+        &self,
+        sdk: &mut KidsnoteSdk,
+        handler: &mut dyn BackupEventHandler,
+        children: &[MeInfoChildrenResponse],
+        child_id: u64,
+        date: &str,
+        report_targets: Vec<&BackupTarget>,
+    ) {
+        let report_ids = report_targets.iter().filter_map(|f| match f {
+            BackupTarget::Report { report_id, .. } => Some(*report_id),
+            BackupTarget::Child(_) => None,
+        });
+
+        let Some(child) = children.iter().find(|f| f.id == child_id) else {
+            log::warn!(target:"backup", "[Child][{}] child not found.", child_id);
+            for report_id in report_ids {
+                handler.on_event(&BackupEvent::ReportNotFound {
+                    child_id,
+                    report_id,
+                });
+            }
+            return;
+        };
+        handler.on_event(&BackupEvent::ChildStarted {
+            child_id,
+            child_name: child.name.clone(),
+        });
+
+        let reports = match sdk.child().get_reports_by_date(child_id, date).await {
+            Ok(reports) => reports,
+            Err(err) => {
+                log::error!(target:"backup", "[Child][{}][report] look up error. date={}, {}", child.name, date, err);
+                handler.on_event(&BackupEvent::Error {
+                    child_id: Some(child_id),
+                    message: format!("[{}] report look up fail. {}", child.name, err),
+                    error_code: err.code(),
+                });
+                handler.on_event(&BackupEvent::ChildFinished {
+                    child_id,
+                    synced: false,
+                });
+                return;
+            }
+        };
+
+        let center_map = Self::center_map(child);
+        let mut download_sources = Vec::new();
+        for target in report_targets {
+            let BackupTarget::Report {
+                report_id,
+                media_ids,
+                content,
+                ..
+            } = target
+            else {
+                continue;
+            };
+            let Some(report) = reports.iter().find(|f| f.id == *report_id) else {
+                log::warn!(target:"backup", "[Child][{}][report][{}] report not found.", child.name, report_id);
+                handler.on_event(&BackupEvent::ReportNotFound {
+                    child_id,
+                    report_id: *report_id,
+                });
+                continue;
+            };
+
+            let mut source = BackupSource::from_report(report.clone(), child, &center_map);
+            // 카드를 다시 그릴 때는 모든 사진이 필요하다. 이미 받은 사진은 건너뛴다.
+            let redraw_card = *content && self.sinks.contains(&BackupSink::ReportCard);
+            if let Some(media_ids) = media_ids.as_ref().filter(|_| !redraw_card) {
+                source
+                    .attached_images
+                    .retain(|image| media_ids.contains(&image.id));
+            }
+            if !content {
+                source.report_content = None;
+                source.raw_report = None;
+            }
+            download_sources.push(source);
+        }
+
+        self.step_report_source_download(sdk, handler, download_sources)
+            .await;
+        handler.on_event(&BackupEvent::ChildFinished {
+            child_id,
+            synced: false,
+        });
+    }
+
+    async fn step_report_source_download(
+        &self,
+        sdk: &mut KidsnoteSdk,
+        handler: &mut dyn BackupEventHandler,
+        sources: Vec<BackupSource>,
+    ) {
+        for mut source in sources {
+            if self.skip_reports.contains(&source.source_id) {
+                log::debug!(target: "backup", "[Child][{}][report][{}] already backed up. skip.", source.child_name, source.source_id);
+                continue;
+            }
+            let report = source.report_ref();
+            handler.on_event(&BackupEvent::ReportDiscovered {
+                report: report.clone(),
+                media_count: source.attached_images.len() as u64,
+            });
+
+            let title = Self::report_title(&source);
+
+            // 댓글은 원문과 함께 저장한다. 받지 못해도 원문은 저장한다.
+            if self.sinks.contains(&BackupSink::Record) && !self.dry_run && source.has_comments() {
+                match sdk.child().get_report_comments(source.source_id).await {
+                    Ok(comments) => source.comments = comments,
+                    Err(err) => {
+                        log::error!(target: "backup", "[Child][{}][report][{}] comment look up error. {}", source.child_name, source.source_id, err);
+                        handler.on_event(&BackupEvent::Error {
+                            child_id: Some(source.child_id),
+                            message: format!(
+                                "[{}] comment look up fail. report={}, {}",
+                                source.child_name, source.source_id, err
+                            ),
+                            error_code: err.code(),
+                        });
+                    }
+                }
+            }
+
+            // 알림장 원문. 내용을 정리하지 않고 조회 결과 그대로 저장한다.
+            if self.sinks.contains(&BackupSink::Record) && !self.dry_run {
+                if let Some(record) = source.record() {
+                    let record_file = self.layout.record_file(&source);
+                    let result = record.save(&record_file);
+                    Self::on_content_result(
+                        handler,
+                        &report,
+                        &record_file.to_string_lossy(),
+                        result,
+                    );
+                }
+            }
+
+            // 알림장 텍스트 변환해서 저장
+            if !self.dry_run {
+                self.step_report_render(handler, &source, &report, &title);
+            }
+
+            // 이미지 다운로드 받기
+            if self.sinks.contains(&BackupSink::Media) {
+                self.step_report_media_download(sdk, handler, &source, &report)
+                    .await;
+            }
+
+            // 사진 카드
+            if self.sinks.contains(&BackupSink::ReportCard) && !self.dry_run {
+                self.step_report_card(handler, &source, &report, &title);
+            }
+
+            // (미구현) 비디오 다운로드 받기
+            // (미구현) 첨부파일 다운로드 받기
+        }
+    }
+
+    /// 받아둔 알림장으로 파일만 다시 만든다. 서버에 요청하지 않는다.
+    /// - .txt, 알림장 이미지, 사진 카드(sink 에 있는 경우)만 만들고 원문, 첨부 이미지는 그대로 둔다.
+    pub fn render(&self, handler: &mut dyn BackupEventHandler, sources: Vec<BackupSource>) {
+        for source in sources {
+            let report = source.report_ref();
+            handler.on_event(&BackupEvent::ReportDiscovered {
+                report: report.clone(),
+                media_count: source.attached_images.len() as u64,
+            });
+            if self.dry_run {
+                continue;
+            }
+            let title = Self::report_title(&source);
+            self.step_report_render(handler, &source, &report, &title);
+            if self.sinks.contains(&BackupSink::ReportCard) {
+                self.step_report_card(handler, &source, &report, &title);
+            }
+        }
+        handler.on_event(&BackupEvent::Finished);
+    }
+
+    fn report_title(source: &BackupSource) -> String {
+        format!(
+            "제목 : {} {}",
+            Utc.from_utc_datetime(&source.report_date.naive_utc())
+                .format("%Y년 %-m월 %-e일"),
+            source.source_type
+        )
+    }
+
+    /// 알림장 텍스트(.txt)와 이미지
+    fn step_report_render(
+        &self,
+        handler: &mut dyn BackupEventHandler,
+        source: &BackupSource,
+        report: &BackupReportRef,
+        title: &str,
+    ) {
+        let Some(contents) = &source.report_content else {
+            return;
+        };
+        if contents.trim().is_empty() {
+            return;
+        }
+        let new_contents = self.theme.text.normalize(contents);
+        if new_contents.is_empty() {
+            return;
+        }
+        let render_report = source.render_report(title, new_contents);
+        if self.sinks.contains(&BackupSink::Text) {
+            let text_file = self.layout.text_file(source);
+            if let Some(output_file) = text_file.to_str() {
+                let result =
+                    ImageTool::render_report_txt_file(&render_report, &self.theme, output_file);
+                Self::on_content_result(handler, report, output_file, result);
+            }
+        }
+        if self.sinks.contains(&BackupSink::RenderedImage) {
+            let image_file = self.layout.image_file(source, self.theme.format);
+            if let Some(output_file) = image_file.to_str() {
+                let result =
+                    ImageTool::render_report_file(&render_report, &self.theme, output_file);
+                Self::on_content_result(handler, report, output_file, result);
+            }
+        }
+    }
+
+    /// 첨부 이미지 다운로드
+    async fn step_report_media_download(
+        &self,
+        sdk: &mut KidsnoteSdk,
+        handler: &mut dyn BackupEventHandler,
+        source: &BackupSource,
+        report: &BackupReportRef,
+    ) {
+        let file_time = FileTime::from_unix_time(source.report_date.timestamp(), 0);
+        let media_ref = source.media_ref();
+        for image in &source.attached_images {
+            let output_file = self.layout.media_file(source, image);
+            let Some(output_file) = output_file.to_str() else {
+                continue;
+            };
+            let fallback_size = self.fallbacks.get(&image.id).copied();
+            if self.dry_run {
+                log::info!(target: "backup", "[Test][Child][{}][report][{}][Image][{}] download.", source.child_name, source.source_id, image.id);
+                handler.on_event(&BackupEvent::MediaSkipped {
+                    report: report.clone(),
+                    media_id: image.id,
+                    path: output_file.to_string(),
+                    variant: fallback_size.map_or(ImageVariant::Original, |_| ImageVariant::Large),
+                });
+                continue;
+            }
+
+            match sdk
+                .download_report_image(&media_ref, image, file_time, output_file, fallback_size)
+                .await
+            {
+                Ok(download_result) => {
+                    if download_result.is_fallback() {
+                        log::warn!(target: "backup", "[Child][{}][report][{}][Image][{}] original unavailable. {:?} image used.", source.child_name, source.source_id, image.id, download_result.variant);
+                    }
+                    if download_result.downloaded {
+                        log::debug!(target: "backup", "[Child][{}][report][{}][Image][{}] download. path={}", source.child_name, source.source_id, image.id, output_file);
+                        handler.on_event(&BackupEvent::MediaDownloaded {
+                            report: report.clone(),
+                            media_id: image.id,
+                            path: output_file.to_string(),
+                            bytes: download_result.bytes,
+                            variant: download_result.variant,
+                            url_refreshed: download_result.url_refreshed,
+                        });
+                    } else {
+                        log::debug!(target: "backup", "[Child][{}][report][{}][Image][{}] download skip. path={}", source.child_name, source.source_id, image.id, output_file);
+                        handler.on_event(&BackupEvent::MediaSkipped {
+                            report: report.clone(),
+                            media_id: image.id,
+                            path: output_file.to_string(),
+                            variant: download_result.variant,
+                        });
+                    }
+                }
+                Err(err) => {
+                    log::error!(target: "backup", "[Child][{}][report][{}][Image][{}] download error. {}", source.child_name, source.source_id, image.id, err);
+                    handler.on_event(&BackupEvent::MediaFailed {
+                        report: report.clone(),
+                        media_id: image.id,
+                        url: image.original.clone(),
+                        path: output_file.to_string(),
+                        error: err.to_string(),
+                        error_code: err.code(),
+                    });
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+
+    /// 알림장 내용과 받아둔 사진으로 카드를 그린다.
+    /// - 받지 못한 사진은 빼고 그린다.
+    fn step_report_card(
+        &self,
+        handler: &mut dyn BackupEventHandler,
+        source: &BackupSource,
+        report: &BackupReportRef,
+        title: &str,
+    ) {
+        let Some(contents) = &source.report_content else {
+            return;
+        };
+        let photo_files: Vec<String> = source
+            .attached_images
+            .iter()
+            .map(|image| self.layout.media_file(source, image))
+            .filter(|f| f.exists())
+            .filter_map(|f| f.to_str().map(str::to_string))
+            .collect();
+        if contents.trim().is_empty() && photo_files.is_empty() {
+            return;
+        }
+
+        let render_report = source.render_report(title, self.theme.text.normalize(contents));
+        let pages = match ImageTool::render_report_card(&render_report, &photo_files, &self.theme) {
+            Ok(pages) => pages,
+            Err(err) => {
+                let output_file = self.layout.card_file(source, self.theme.format, None);
+                Self::on_content_result(handler, report, &output_file.to_string_lossy(), Err(err));
+                return;
+            }
+        };
+        let page_count = pages.len();
+        for (index, page) in pages.iter().enumerate() {
+            let page_number = Some(index + 1).filter(|_| page_count > 1);
+            let output_file = self
+                .layout
+                .card_file(source, self.theme.format, page_number);
+            let Some(output_file) = output_file.to_str() else {
+                continue;
+            };
+            let result =
+                ImageTool::save_report_image(page, &self.theme, output_file, source.report_date);
+            Self::on_content_result(handler, report, output_file, result);
+        }
+        self.remove_stale_cards(source, page_count);
+    }
+
+    /// 쪽 수가 바뀌어서 남은 이전 카드 파일을 지운다.
+    /// - 한 장이면 쪽 번호가 붙은 파일, 여러 장이면 쪽 번호가 없는 파일과 page_count 다음 쪽부터 지운다.
+    fn remove_stale_cards(&self, source: &BackupSource, page_count: usize) {
+        let mut stale_files = Vec::new();
+        if page_count > 1 {
+            stale_files.push(self.layout.card_file(source, self.theme.format, None));
+        }
+        let first_stale_page = if page_count > 1 { page_count + 1 } else { 1 };
+        for page in first_stale_page.. {
+            let card_file = self.layout.card_file(source, self.theme.format, Some(page));
+            if !card_file.exists() {
+                break;
+            }
+            stale_files.push(card_file);
+        }
+        for card_file in stale_files.iter().filter(|f| f.exists()) {
+            match std::fs::remove_file(card_file) {
+                Ok(_) => {
+                    log::info!(target: "backup", "[Child][{}][report][{}][Content] stale card removed. path={}", source.child_name, source.source_id, card_file.display())
+                }
+                Err(err) => {
+                    log::warn!(target: "backup", "[Child][{}][report][{}][Content] stale card remove fail. path={}, {}", source.child_name, source.source_id, card_file.display(), err)
+                }
+            }
+        }
+    }
+
+    fn on_content_result(
+        handler: &mut dyn BackupEventHandler,
+        report: &datatypes::BackupReportRef,
+        output_file: &str,
+        result: Result<(), AuthError>,
+    ) {
+        match result {
+            Ok(_) => {
+                log::debug!(target: "backup", "[Child][{}][report][{}][Content] save. path={}", report.child_name, report.report_id, output_file);
+                handler.on_event(&BackupEvent::ContentSaved {
+                    report: report.clone(),
+                    path: output_file.to_string(),
+                });
+            }
+            Err(err) => {
+                log::error!(target: "backup", "[Child][{}][report][{}][Content] save error. {}", report.child_name, report.report_id, err);
+                handler.on_event(&BackupEvent::ContentFailed {
+                    report: report.clone(),
+                    path: output_file.to_string(),
+                    error: err.to_string(),
+                });
+            }
+        }
+    }
+}
@@ -1,54 +1,177 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::resource::datatypes::ResourceImageResponse;
+use crate::{
+    resource::datatypes::ResourceImageResponse,
+    schema::{extra_fields, null_default, ExtraFields, UnknownFields},
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChildReportResponse {
+    #[serde(default, deserialize_with = "null_default")]
     pub count: i32,
     pub next: Option<String>,
     pub previous: Option<String>,
+    #[serde(default, deserialize_with = "null_default")]
     pub results: Vec<ChildReportDataResponse>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ChildReportDataResponse {
     pub id: u64,
     pub created: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_default")]
     pub modified: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub date_written: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub author: ChildReportAuthorResponse,
+    #[serde(default, deserialize_with = "null_default")]
     pub author_name: String,
     pub center: Option<u64>,
+    #[serde(default, deserialize_with = "null_default")]
     pub cls: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub class_name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub child: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub child_name: String,
     pub child_picture: Option<ResourceImageResponse>,
+    #[serde(default, deserialize_with = "null_default")]
     pub is_sent_from_center: bool,
     pub content: Option<String>,
     pub weather: Option<String>,
     //pub attached_video: Vec<String>,
+    #[serde(default, deserialize_with = "null_default")]
     pub num_comments: i32,
+    #[serde(default, deserialize_with = "null_default")]
     pub read_by_me: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub read_by_parent: ChildReportReadByParentResponse,
+    #[serde(default, deserialize_with = "null_default")]
     pub attached_images: Vec<ResourceImageResponse>,
     //pub attached_files: Vec<String>,
     pub thumbnail: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ChildReportAuthorResponse {
+    #[serde(default, deserialize_with = "null_default")]
     pub id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub r#type: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub name: String,
     pub picture: Option<ResourceImageResponse>,
+    #[serde(default, deserialize_with = "null_default")]
     pub username: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct ChildReportReadByParentResponse {
+    #[serde(default, deserialize_with = "null_default")]
     pub date_read: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl UnknownFields for ChildReportResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.results
+            .unknown_fields(&format!("{}.results", path), fields);
+    }
+}
+
+impl UnknownFields for ChildReportDataResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.author
+            .unknown_fields(&format!("{}.author", path), fields);
+        self.child_picture
+            .unknown_fields(&format!("{}.child_picture", path), fields);
+        self.read_by_parent
+            .unknown_fields(&format!("{}.read_by_parent", path), fields);
+        self.attached_images
+            .unknown_fields(&format!("{}.attached_images", path), fields);
+    }
+}
+
+impl UnknownFields for ChildReportAuthorResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.picture
+            .unknown_fields(&format!("{}.picture", path), fields);
+    }
+}
+
+impl UnknownFields for ChildReportReadByParentResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+    }
+}
+
+/// 알림장 댓글 목록
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportCommentResponse {
+    #[serde(default, deserialize_with = "null_default")]
+    pub count: i32,
+    pub next: Option<String>,
+    pub previous: Option<String>,
+    #[serde(default, deserialize_with = "null_default")]
+    pub results: Vec<ReportCommentDataResponse>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportCommentDataResponse {
+    pub id: u64,
+    pub created: DateTime<Utc>,
+    #[serde(default, deserialize_with = "null_default")]
+    pub modified: String,
+    #[serde(default, deserialize_with = "null_default")]
+    pub author: ChildReportAuthorResponse,
+    #[serde(default, deserialize_with = "null_default")]
+    pub author_name: String,
+    #[serde(default, deserialize_with = "null_default")]
+    pub content: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl ReportCommentDataResponse {
+    /// 작성자 이름. author_name 이 비어 있으면 author.name
+    pub fn writer_name(&self) -> &str {
+        if self.author_name.is_empty() {
+            &self.author.name
+        } else {
+            &self.author_name
+        }
+    }
+}
+
+impl UnknownFields for ReportCommentResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.results
+            .unknown_fields(&format!("{}.results", path), fields);
+    }
+}
+
+impl UnknownFields for ReportCommentDataResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.author
+            .unknown_fields(&format!("{}.author", path), fields);
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -75,3 +198,9 @@ impl GetReportsParam {
         }
     }
 }
+
+impl Default for GetReportsParam {
+    fn default() -> Self {
+        Self::new()
+    }
+}
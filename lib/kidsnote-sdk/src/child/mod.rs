@@ -1,37 +1,74 @@
 pub mod datatypes;
 
-use datatypes::ChildReportResponse;
+use chrono::{DateTime, FixedOffset, Utc};
+use datatypes::{
+    ChildReportDataResponse, ChildReportResponse, ReportCommentDataResponse, ReportCommentResponse,
+};
 use std::sync::{Arc, Mutex};
 
-use crate::{auth::error_types::AuthError, options::KidsnoteOptions};
+use crate::{
+    archive::ResponseArchive,
+    auth::{
+        error_types::{AuthError, AuthErrorCode},
+        KidsnoteAuthSdk,
+    },
+    options::KidsnoteOptions,
+    schema::decode_response,
+};
 
 use self::datatypes::GetReportsParam;
 
 pub struct KidsnoteChildSdk {
     options: Arc<Mutex<KidsnoteOptions>>,
+    auth: KidsnoteAuthSdk,
+    archive: Option<ResponseArchive>,
 }
 
 impl KidsnoteChildSdk {
-    pub fn new(config: Arc<Mutex<KidsnoteOptions>>) -> KidsnoteChildSdk {
-        Self { options: config }
+    pub fn new(config: Arc<Mutex<KidsnoteOptions>>, auth: KidsnoteAuthSdk) -> KidsnoteChildSdk {
+        Self {
+            options: config,
+            auth,
+            archive: None,
+        }
+    }
+
+    /// 응답 원문 보관. None 이면 보관하지 않는다.
+    pub fn set_response_archive(&mut self, archive: Option<ResponseArchive>) {
+        self.archive = archive;
     }
 
     /// 알림장 조회
+    /// - access token 이 만료되었거나 401 이면 세션을 갱신하고 한 번 더 요청한다.
     pub async fn get_reports(
         &self,
         child_id: u64,
         param: Option<GetReportsParam>,
     ) -> Result<ChildReportResponse, AuthError> {
-        let options = self.options.lock().unwrap();
-        let access_token = options.get_access_token_or_error()?;
+        self.auth.ensure_session().await?;
+        match self.request_reports(child_id, &param).await {
+            Err(err) if err.code() == Some(AuthErrorCode::Unauthorized) => {
+                log::warn!(target:"auth", "unauthorized. refresh session and retry. {}", err);
+                self.auth.refresh_session().await?;
+                self.request_reports(child_id, &param).await
+            }
+            result => result,
+        }
+    }
+
+    async fn request_reports(
+        &self,
+        child_id: u64,
+        param: &Option<GetReportsParam>,
+    ) -> Result<ChildReportResponse, AuthError> {
+        let (access_token, host) = {
+            let options = self.options.lock().unwrap();
+            (options.get_access_token_or_error()?, options.get_host())
+        };
 
-        let query = serde_urlencoded::to_string(&param).unwrap();
-        let url = format!(
-            "{}/v1_2/children/{}/reports/?{}",
-            options.get_host_ref(),
-            child_id,
-            query
-        );
+        let archive_key = ResponseArchive::reports_key(&param.clone().unwrap_or_default());
+        let query = serde_urlencoded::to_string(param).unwrap();
+        let url = format!("{}/v1_2/children/{}/reports/?{}", host, child_id, query);
 
         let client = crate::common::get_client();
         let response = client
@@ -48,18 +85,23 @@ impl KidsnoteChildSdk {
         match response {
             Ok(response) => {
                 if response.status().is_success() {
-                    match response.json::<ChildReportResponse>().await {
-                        Ok(result) => Ok(result),
-                        Err(err) => Err(AuthError::GeneralErrorStr(format!(
-                            "parse error. {:?}",
-                            err
-                        ))),
+                    let body = response.bytes().await.map_err(|err| {
+                        AuthError::GeneralErrorStr(format!("parse error. {:?}", err))
+                    })?;
+                    if let Some(archive) = &self.archive {
+                        archive.record(
+                            &ResponseArchive::reports_endpoint(child_id),
+                            &archive_key,
+                            &body,
+                        );
                     }
+                    decode_response::<ChildReportResponse>("reports", &body)
                 } else {
-                    Err(AuthError::GeneralErrorStr(format!(
-                        "status error. status={:?}",
-                        response.status()
-                    )))
+                    let status = response.status();
+                    Err(AuthError::ErrorWithCodeString(
+                        AuthErrorCode::from_u16(status.as_u16()),
+                        format!("status error. status={:?}", status),
+                    ))
                 }
             }
             Err(err) => Err(AuthError::GeneralErrorStr(format!(
@@ -68,4 +110,161 @@ impl KidsnoteChildSdk {
             ))),
         }
     }
+
+    /// 알림장 댓글 전체 조회
+    /// - access token 이 만료되었거나 401 이면 세션을 갱신하고 한 번 더 요청한다.
+    pub async fn get_report_comments(
+        &self,
+        report_id: u64,
+    ) -> Result<Vec<ReportCommentDataResponse>, AuthError> {
+        self.auth.ensure_session().await?;
+        let mut comments = Vec::new();
+        let mut page: Option<String> = None;
+        for _ in 0..1000 {
+            let result = match self.request_comments(report_id, &page).await {
+                Err(err) if err.code() == Some(AuthErrorCode::Unauthorized) => {
+                    log::warn!(target:"auth", "unauthorized. refresh session and retry. {}", err);
+                    self.auth.refresh_session().await?;
+                    self.request_comments(report_id, &page).await
+                }
+                result => result,
+            }?;
+            let is_last = result.results.is_empty() || result.next.is_none();
+            comments.extend(result.results);
+            if is_last {
+                break;
+            }
+            page = result.next;
+        }
+        Ok(comments)
+    }
+
+    async fn request_comments(
+        &self,
+        report_id: u64,
+        page: &Option<String>,
+    ) -> Result<ReportCommentResponse, AuthError> {
+        let (access_token, host) = {
+            let options = self.options.lock().unwrap();
+            (options.get_access_token_or_error()?, options.get_host())
+        };
+
+        let query = serde_urlencoded::to_string([("page", page)]).unwrap();
+        let url = format!("{}/v1/reports/{}/comments/?{}", host, report_id, query);
+
+        let client = crate::common::get_client();
+        let response = client
+            .get(url)
+            .header("Content-Type", "application/json")
+            .header(
+                "Authorization",
+                format!("{} {}", access_token.r#type, access_token.token),
+            )
+            .send()
+            .await;
+        match response {
+            Ok(response) => {
+                if response.status().is_success() {
+                    let body = response.bytes().await.map_err(|err| {
+                        AuthError::GeneralErrorStr(format!("parse error. {:?}", err))
+                    })?;
+                    if let Some(archive) = &self.archive {
+                        archive.record(
+                            &ResponseArchive::comments_endpoint(report_id),
+                            page.as_deref().unwrap_or("1"),
+                            &body,
+                        );
+                    }
+                    decode_response::<ReportCommentResponse>("comments", &body)
+                } else {
+                    let status = response.status();
+                    Err(AuthError::ErrorWithCodeString(
+                        AuthErrorCode::from_u16(status.as_u16()),
+                        format!("status error. status={:?}", status),
+                    ))
+                }
+            }
+            Err(err) => Err(AuthError::GeneralErrorStr(format!(
+                "unknown error. {:?}",
+                err
+            ))),
+        }
+    }
+
+    /// 해당 날짜(Asia/Seoul)의 알림장 전체 조회
+    pub async fn get_reports_by_date(
+        &self,
+        child_id: u64,
+        date: &str,
+    ) -> Result<Vec<ChildReportDataResponse>, AuthError> {
+        let mut param = GetReportsParam::new();
+        param.date_start = Some(date.to_string());
+        param.date_end = Some(date.to_string());
+        param.tz = Some("Asia/Seoul".to_string());
+
+        let mut reports = Vec::new();
+        self.get_reports_pages(child_id, param, |page| {
+            reports.extend(page.results.iter().cloned());
+        })
+        .await?;
+        Ok(reports)
+    }
+
+    /// 알림장 전체 페이지 조회. 페이지마다 on_page 를 호출하고 페이지 수를 돌려준다.
+    /// - param.page_size 를 크게 하면 요청 수가 줄어든다.
+    pub async fn get_reports_pages<F>(
+        &self,
+        child_id: u64,
+        mut param: GetReportsParam,
+        mut on_page: F,
+    ) -> Result<u64, AuthError>
+    where
+        F: FnMut(&ChildReportResponse),
+    {
+        let mut pages = 0;
+        for _ in 0..10000 {
+            let result = self.get_reports(child_id, Some(param.clone())).await?;
+            pages += 1;
+            on_page(&result);
+            if result.results.is_empty() || result.next.is_none() {
+                break;
+            }
+            param.page = result.next;
+        }
+        Ok(pages)
+    }
+
+    /// 알림장 한 건 조회
+    /// - 알림장 작성일(Asia/Seoul)로 조회한 뒤 id 로 찾는다.
+    pub async fn get_report(
+        &self,
+        child_id: u64,
+        report_id: u64,
+        report_date: DateTime<Utc>,
+    ) -> Result<Option<ChildReportDataResponse>, AuthError> {
+        let date = Self::report_local_date(report_date);
+        let reports = self.get_reports_by_date(child_id, &date).await?;
+        Ok(reports.into_iter().find(|f| f.id == report_id))
+    }
+
+    /// 알림장 조회 시 사용하는 날짜 (Asia/Seoul)
+    pub fn report_local_date(report_date: DateTime<Utc>) -> String {
+        let offset = FixedOffset::east_opt(9 * 3600).unwrap();
+        report_date
+            .with_timezone(&offset)
+            .format("%Y-%m-%d")
+            .to_string()
+    }
+
+    /// 알림장 날씨 이름. 모르는 값은 그대로 쓴다.
+    pub fn weather_label(weather: &str) -> String {
+        match weather {
+            "sunny" => String::from("맑음"),
+            "partly_cloudy" | "partly-cloudy" => String::from("구름 조금"),
+            "cloudy" => String::from("흐림"),
+            "rainy" | "rain" => String::from("비"),
+            "snowy" | "snow" => String::from("눈"),
+            _ => weather.to_string(),
+        }
+    }
 }
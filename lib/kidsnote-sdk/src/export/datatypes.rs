@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// html 내보내기 결과
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct HtmlExportResult {
+    pub children: u64,
+    pub reports: u64,
+    /// 사진첩에 넣은 사진
+    pub photos: u64,
+    /// 받지 않아서 빠진 사진
+    pub photos_missing: u64,
+    /// 만든 html 파일
+    pub pages: u64,
+    pub errors: Vec<String>,
+}
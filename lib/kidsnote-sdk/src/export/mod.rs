@@ -0,0 +1,551 @@
+pub mod datatypes;
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Datelike, FixedOffset, Utc};
+
+use crate::{
+    auth::error_types::AuthError,
+    backup::datatypes::{BackupLayout, BackupSource},
+    child::KidsnoteChildSdk,
+    tool::image_tool::ImageTool,
+};
+
+use self::datatypes::HtmlExportResult;
+
+/// 모든 페이지가 같이 쓰는 스타일. 사진 확대(lightbox)는 :target 으로 만들어서 스크립트 없이 동작한다.
+const STYLE_CSS: &str = r#"* { box-sizing: border-box; }
+body { margin: 0; font-family: "Apple SD Gothic Neo", "Malgun Gothic", "Nanum Gothic", sans-serif; background: #f6f4ef; color: #333; line-height: 1.6; }
+main { max-width: 860px; margin: 0 auto; padding: 24px 16px 64px; }
+a { color: #2f6fbf; text-decoration: none; }
+nav.top { display: flex; justify-content: space-between; gap: 12px; margin-bottom: 16px; }
+h1 { font-size: 1.6em; margin: 0 0 8px; }
+h2 { font-size: 1.3em; margin: 32px 0 8px; border-bottom: 2px solid #e2ddd2; }
+h3 { font-size: 1.05em; margin: 20px 0 8px; color: #666; }
+.summary, .meta { color: #777; font-size: 0.9em; }
+.years a { margin-right: 12px; }
+ul.children, ul.timeline { list-style: none; padding: 0; margin: 0; }
+ul.children li, ul.timeline li { background: #fff; border-radius: 8px; margin-bottom: 8px; }
+ul.children a, ul.timeline a { display: flex; gap: 12px; align-items: center; padding: 8px; color: inherit; }
+ul.timeline img, ul.timeline .no-photo { width: 64px; height: 64px; border-radius: 6px; object-fit: cover; background: #e2ddd2; flex: none; }
+ul.timeline .date { font-weight: bold; white-space: nowrap; }
+ul.timeline .snippet { flex: 1; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; }
+ul.timeline .count { color: #999; font-size: 0.85em; white-space: nowrap; }
+article { background: #fff; border-radius: 8px; padding: 24px; }
+dl.meta { display: grid; grid-template-columns: max-content 1fr; gap: 2px 12px; margin: 0 0 16px; }
+dl.meta dt { font-weight: bold; }
+dl.meta dd { margin: 0; }
+.content { white-space: pre-wrap; word-break: break-word; font-size: 1.05em; }
+.gallery { display: grid; grid-template-columns: repeat(auto-fill, minmax(150px, 1fr)); gap: 6px; }
+.gallery img { width: 100%; aspect-ratio: 1; object-fit: cover; border-radius: 4px; display: block; }
+ul.comments { list-style: none; padding: 0; margin: 0 0 16px; }
+ul.comments li { border-top: 1px solid #eee; padding: 8px 0; }
+ul.comments .writer { font-weight: bold; margin-right: 8px; }
+ul.comments .content { font-size: 1em; }
+.lightbox { display: none; position: fixed; inset: 0; background: rgba(0, 0, 0, 0.9); z-index: 10; align-items: center; justify-content: center; }
+.lightbox:target { display: flex; }
+.lightbox img { max-width: 92vw; max-height: 88vh; }
+.lightbox a { position: absolute; color: #fff; font-size: 2.4em; padding: 8px 20px; }
+.lightbox .close { top: 0; right: 0; }
+.lightbox .prev { left: 0; top: 45%; }
+.lightbox .next { right: 0; top: 45%; }
+.lightbox .download { bottom: 0; right: 0; font-size: 1em; }
+"#;
+
+/// 받아둔 알림장으로 자녀별 정적 웹 페이지를 만든다.
+/// - {dir}/index.html : 자녀 목록
+/// - {dir}/{자녀}/index.html : 연/월별 알림장 목록
+/// - {dir}/{자녀}/reports/{yyyymmdd}_{id}.html : 알림장 내용, 날씨, 댓글, 사진첩
+/// - 사진은 {자녀}/photos 에 복사하고 {자녀}/thumbs/{크기} 에 작은 사진을 만들어서 폴더만 옮겨도 열 수 있다.
+pub struct HtmlExport {
+    layout: BackupLayout,
+    export_dir: PathBuf,
+    thumbnail_size: u32,
+}
+
+impl HtmlExport {
+    pub fn new<P: Into<PathBuf>>(layout: BackupLayout, export_dir: P) -> HtmlExport {
+        Self {
+            layout,
+            export_dir: export_dir.into(),
+            thumbnail_size: 240,
+        }
+    }
+
+    /// 작은 사진 크기 (px)
+    pub fn thumbnail_size(mut self, thumbnail_size: u32) -> Self {
+        self.thumbnail_size = thumbnail_size;
+        self
+    }
+
+    pub fn export(&self, sources: &[BackupSource]) -> HtmlExportResult {
+        let mut result = HtmlExportResult::default();
+        let mut children: BTreeMap<(String, u64), Vec<&BackupSource>> = BTreeMap::new();
+        for source in sources {
+            children
+                .entry((source.child_name.clone(), source.child_id))
+                .or_default()
+                .push(source);
+        }
+
+        if let Err(err) = Self::write_file(&self.export_dir.join("style.css"), STYLE_CSS) {
+            result.errors.push(err.to_string());
+        }
+        let mut child_items = Vec::new();
+        for ((child_name, _), mut reports) in children {
+            reports.sort_by_key(|f| std::cmp::Reverse((f.report_date, f.source_id)));
+            let child_dir = Self::file_name(&child_name);
+            self.export_child(&child_name, &child_dir, &reports, &mut result);
+            child_items.push(format!(
+                r#"<li><a href="{}/index.html"><strong>{}</strong><span class="summary">알림장 {}개 · {} ~ {}</span></a></li>"#,
+                Self::escape(&child_dir),
+                Self::escape(&child_name),
+                reports.len(),
+                Self::local_date(reports.last().unwrap().report_date).format("%Y-%m-%d"),
+                Self::local_date(reports[0].report_date).format("%Y-%m-%d"),
+            ));
+            result.children += 1;
+        }
+
+        let body = format!(
+            "<h1>키즈노트 알림장</h1>\n<ul class=\"children\">\n{}\n</ul>",
+            child_items.join("\n")
+        );
+        self.write_page(
+            &self.export_dir.join("index.html"),
+            "키즈노트 알림장",
+            "",
+            &body,
+            &mut result,
+        );
+        result
+    }
+
+    fn export_child(
+        &self,
+        child_name: &str,
+        child_dir: &str,
+        reports: &[&BackupSource],
+        result: &mut HtmlExportResult,
+    ) {
+        let child_path = self.export_dir.join(child_dir);
+        let mut years: BTreeMap<i32, BTreeMap<u32, Vec<String>>> = BTreeMap::new();
+        for (index, source) in reports.iter().enumerate() {
+            // 목록은 최신순이라 이전 알림장이 다음 항목이다.
+            let newer = index.checked_sub(1).map(|f| reports[f]);
+            let older = reports.get(index + 1).copied();
+            let photos = self.export_photos(&child_path, source, result);
+            let page_name = Self::report_page(source);
+            self.export_report(
+                &child_path,
+                child_name,
+                source,
+                &photos,
+                newer,
+                older,
+                result,
+            );
+
+            let date = Self::local_date(source.report_date);
+            let thumbnail = match photos.first() {
+                Some((_, thumb)) => format!(r#"<img src="{}" alt="" loading="lazy">"#, thumb),
+                None => String::from(r#"<span class="no-photo"></span>"#),
+            };
+            let snippet = source
+                .report_content
+                .as_deref()
+                .and_then(|f| f.lines().map(str::trim).find(|f| !f.is_empty()))
+                .unwrap_or("");
+            years
+                .entry(date.year())
+                .or_default()
+                .entry(date.month())
+                .or_default()
+                .push(format!(
+                    r#"<li><a href="reports/{}">{}<span class="date">{}</span><span class="snippet">{}</span><span class="count">{}</span></a></li>"#,
+                    page_name,
+                    thumbnail,
+                    Self::date_label(source.report_date, false),
+                    Self::escape(snippet),
+                    Self::photo_count_label(photos.len()),
+                ));
+        }
+
+        let year_links: Vec<String> = years
+            .keys()
+            .rev()
+            .map(|year| format!(r##"<a href="#y{0}">{0}년</a>"##, year))
+            .collect();
+        let mut body = format!(
+            "<nav class=\"top\"><a href=\"../index.html\">← 전체</a></nav>\n<h1>{} 알림장</h1>\n<p class=\"summary\">알림장 {}개</p>\n<p class=\"years\">{}</p>\n",
+            Self::escape(child_name),
+            reports.len(),
+            year_links.join(" ")
+        );
+        for (year, months) in years.iter().rev() {
+            body.push_str(&format!("<h2 id=\"y{0}\">{0}년</h2>\n", year));
+            for (month, items) in months.iter().rev() {
+                body.push_str(&format!(
+                    "<h3 id=\"m{}-{:02}\">{}월 ({}개)</h3>\n<ul class=\"timeline\">\n{}\n</ul>\n",
+                    year,
+                    month,
+                    month,
+                    items.len(),
+                    items.join("\n")
+                ));
+            }
+        }
+        self.write_page(
+            &child_path.join("index.html"),
+            &format!("{} 알림장", child_name),
+            "../",
+            &body,
+            result,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn export_report(
+        &self,
+        child_path: &Path,
+        child_name: &str,
+        source: &BackupSource,
+        photos: &[(String, String)],
+        newer: Option<&BackupSource>,
+        older: Option<&BackupSource>,
+        result: &mut HtmlExportResult,
+    ) {
+        let title = format!(
+            "{} {}",
+            Self::date_label(source.report_date, true),
+            source.source_type
+        );
+        let mut meta = vec![("작성자", source.author_name.clone())];
+        let center = [
+            source.center_name.as_deref().unwrap_or(""),
+            &source.class_name,
+        ]
+        .iter()
+        .filter(|f| !f.is_empty())
+        .cloned()
+        .collect::<Vec<&str>>()
+        .join(" ");
+        if !center.is_empty() {
+            meta.push(("원/반", center));
+        }
+        if let Some(weather) = source.weather.as_deref().filter(|f| !f.is_empty()) {
+            meta.push(("날씨", KidsnoteChildSdk::weather_label(weather)));
+        }
+        if let Some(read_at) = source
+            .read_at
+            .as_deref()
+            .and_then(|f| DateTime::parse_from_rfc3339(f).ok())
+        {
+            meta.push((
+                "읽음",
+                read_at
+                    .with_timezone(&Self::local_offset())
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string(),
+            ));
+        }
+        let meta: Vec<String> = meta
+            .iter()
+            .map(|(key, value)| format!("<dt>{}</dt><dd>{}</dd>", key, Self::escape(value)))
+            .collect();
+
+        let nav_link = |source: Option<&BackupSource>, label: &str| match source {
+            Some(source) => format!(r#"<a href="{}">{}</a>"#, Self::report_page(source), label),
+            None => String::from("<span></span>"),
+        };
+        let mut body = format!(
+            "<nav class=\"top\">{}<a href=\"../index.html\">{} 알림장 목록</a>{}</nav>\n<article>\n<h1>{}</h1>\n<dl class=\"meta\">{}</dl>\n<div class=\"content\">{}</div>\n",
+            nav_link(older, "← 이전"),
+            Self::escape(child_name),
+            nav_link(newer, "다음 →"),
+            Self::escape(&title),
+            meta.join(""),
+            Self::escape(source.report_content.as_deref().unwrap_or("").trim())
+        );
+
+        // 받지 않은 댓글은 개수만 표시한다.
+        let num_comments = source
+            .raw_report
+            .as_ref()
+            .map(|f| f.num_comments.max(0) as usize)
+            .unwrap_or(0)
+            .max(source.comments.len());
+        if num_comments > 0 {
+            body.push_str(&format!("<h2>댓글 {}개</h2>\n", num_comments));
+        }
+        if !source.comments.is_empty() {
+            body.push_str("<ul class=\"comments\">\n");
+            for comment in &source.comments {
+                body.push_str(&format!(
+                    "<li><span class=\"writer\">{}</span><span class=\"meta\">{}</span><div class=\"content\">{}</div></li>\n",
+                    Self::escape(comment.writer_name()),
+                    Self::local_date(comment.created).format("%Y-%m-%d %H:%M"),
+                    Self::escape(comment.content.trim())
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+        let missing = num_comments - source.comments.len();
+        if missing > 0 {
+            body.push_str(&format!(
+                "<p class=\"summary\">받지 않은 댓글 {}개는 키즈노트 앱에서 볼 수 있습니다.</p>\n",
+                missing
+            ));
+        }
+
+        if !photos.is_empty() {
+            body.push_str(&format!(
+                "<h2>사진 {}장</h2>\n<div class=\"gallery\">\n",
+                photos.len()
+            ));
+            for (index, (_, thumb)) in photos.iter().enumerate() {
+                body.push_str(&format!(
+                    "<a href=\"#photo-{}\"><img src=\"../{}\" alt=\"\" loading=\"lazy\"></a>\n",
+                    index + 1,
+                    thumb
+                ));
+            }
+            body.push_str("</div>\n");
+        }
+        let missing = source.attached_images.len().saturating_sub(photos.len());
+        if missing > 0 {
+            body.push_str(&format!(
+                "<p class=\"summary\">받지 않은 사진 {}장은 빠져 있습니다.</p>\n",
+                missing
+            ));
+        }
+        body.push_str("</article>\n");
+
+        // 사진 확대
+        for (index, (photo, _)) in photos.iter().enumerate() {
+            let number = index + 1;
+            body.push_str(&format!(
+                "<div class=\"lightbox\" id=\"photo-{}\"><a class=\"close\" href=\"#_\">×</a>",
+                number
+            ));
+            if number > 1 {
+                body.push_str(&format!(
+                    "<a class=\"prev\" href=\"#photo-{}\">‹</a>",
+                    number - 1
+                ));
+            }
+            body.push_str(&format!(
+                r#"<img src="../{0}" alt=""><a class="download" href="../{0}" download>원본</a>"#,
+                photo
+            ));
+            if number < photos.len() {
+                body.push_str(&format!(
+                    "<a class=\"next\" href=\"#photo-{}\">›</a>",
+                    number + 1
+                ));
+            }
+            body.push_str("</div>\n");
+        }
+
+        self.write_page(
+            &child_path.join("reports").join(Self::report_page(source)),
+            &title,
+            "../../",
+            &body,
+            result,
+        );
+        result.reports += 1;
+    }
+
+    /// 받아둔 첨부 사진을 복사하고 작은 사진을 만든다.
+    /// - 자녀 폴더 기준 (photos/.., thumbs/..) 경로를 돌려준다.
+    fn export_photos(
+        &self,
+        child_path: &Path,
+        source: &BackupSource,
+        result: &mut HtmlExportResult,
+    ) -> Vec<(String, String)> {
+        let mut photos = Vec::new();
+        for image in &source.attached_images {
+            let media_file = self.layout.media_file(source, image);
+            let Some(file_name) = media_file.file_name().and_then(|f| f.to_str()) else {
+                continue;
+            };
+            if !media_file.exists() {
+                result.photos_missing += 1;
+                continue;
+            }
+            let photo = format!("photos/{}", file_name);
+            // 확장자만 다른 사진이 겹치지 않도록 파일 이름 전체를, 크기를 바꾸면 다시 만들도록 크기를 넣는다.
+            let thumb = format!("thumbs/{}/{}.jpg", self.thumbnail_size, file_name);
+            if let Err(err) = Self::copy_file(&media_file, &child_path.join(&photo)) {
+                result.errors.push(err.to_string());
+                continue;
+            }
+            if let Err(err) = self.make_thumbnail(&media_file, &child_path.join(&thumb)) {
+                // 작은 사진을 만들 수 없으면 원본을 보여준다.
+                log::warn!(target: "export", "{}", err);
+                photos.push((photo.clone(), photo));
+                result.photos += 1;
+                continue;
+            }
+            photos.push((photo, thumb));
+            result.photos += 1;
+        }
+        photos
+    }
+
+    /// 같은 크기의 파일이 있으면 복사하지 않는다.
+    fn copy_file(from: &Path, to: &Path) -> Result<(), AuthError> {
+        let same_size = match (fs::metadata(from), fs::metadata(to)) {
+            (Ok(from), Ok(to)) => from.len() == to.len(),
+            _ => false,
+        };
+        if same_size {
+            return Ok(());
+        }
+        Self::create_parent_dir(to)?;
+        fs::copy(from, to).map(|_| ()).map_err(|err| {
+            AuthError::GeneralErrorStr(format!("photo copy fail. path={}, {}", to.display(), err))
+        })
+    }
+
+    /// 이미 만든 같은 크기의 작은 사진은 다시 만들지 않는다.
+    fn make_thumbnail(&self, photo_file: &Path, thumb_file: &Path) -> Result<(), AuthError> {
+        if thumb_file.exists() {
+            return Ok(());
+        }
+        let thumb_error = |err: String| {
+            AuthError::GeneralErrorStr(format!(
+                "thumbnail fail. path={}, {}",
+                photo_file.display(),
+                err
+            ))
+        };
+        let photo = image::open(photo_file)
+            .map_err(|err| thumb_error(err.to_string()))?
+            .to_rgb8();
+        let thumb = ImageTool::square_thumbnail(&photo, self.thumbnail_size);
+        Self::create_parent_dir(thumb_file)?;
+        thumb
+            .save_with_format(thumb_file, image::ImageFormat::Jpeg)
+            .map_err(|err| thumb_error(err.to_string()))
+    }
+
+    fn write_page(
+        &self,
+        file_path: &Path,
+        title: &str,
+        root: &str,
+        body: &str,
+        result: &mut HtmlExportResult,
+    ) {
+        let html = format!(
+            "<!DOCTYPE html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<link rel=\"stylesheet\" href=\"{}style.css\">\n</head>\n<body>\n<main>\n{}</main>\n</body>\n</html>\n",
+            Self::escape(title),
+            root,
+            body
+        );
+        match Self::write_file(file_path, &html) {
+            Ok(_) => result.pages += 1,
+            Err(err) => result.errors.push(err.to_string()),
+        }
+    }
+
+    fn write_file(file_path: &Path, contents: &str) -> Result<(), AuthError> {
+        Self::create_parent_dir(file_path)?;
+        fs::write(file_path, contents).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "export save fail. path={}, {}",
+                file_path.display(),
+                err
+            ))
+        })
+    }
+
+    fn create_parent_dir(file_path: &Path) -> Result<(), AuthError> {
+        let Some(parent_dir) = file_path.parent() else {
+            return Ok(());
+        };
+        fs::create_dir_all(parent_dir).map_err(|err| {
+            AuthError::GeneralErrorStr(format!(
+                "export dir create fail. path={}, {}",
+                parent_dir.display(),
+                err
+            ))
+        })
+    }
+
+    fn report_page(source: &BackupSource) -> String {
+        format!(
+            "{}_{}.html",
+            Self::local_date(source.report_date).format("%Y%m%d"),
+            source.source_id
+        )
+    }
+
+    /// 2023년 1월 10일 (화). with_year 가 false 면 1월 10일 (화)
+    fn date_label(report_date: DateTime<Utc>, with_year: bool) -> String {
+        let date = Self::local_date(report_date);
+        let weekday = ["월", "화", "수", "목", "금", "토", "일"]
+            [date.weekday().num_days_from_monday() as usize];
+        if with_year {
+            format!("{} ({})", date.format("%Y년 %-m월 %-d일"), weekday)
+        } else {
+            format!("{} ({})", date.format("%-m월 %-d일"), weekday)
+        }
+    }
+
+    fn photo_count_label(count: usize) -> String {
+        if count == 0 {
+            String::new()
+        } else {
+            format!("사진 {}", count)
+        }
+    }
+
+    fn local_offset() -> FixedOffset {
+        FixedOffset::east_opt(9 * 3600).unwrap()
+    }
+
+    fn local_date(report_date: DateTime<Utc>) -> DateTime<FixedOffset> {
+        report_date.with_timezone(&Self::local_offset())
+    }
+
+    /// 폴더 이름에 쓸 수 없는 글자는 _ 로 바꾼다.
+    fn file_name(name: &str) -> String {
+        let name: String = name
+            .trim()
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '%' => '_',
+                c if c.is_control() => '_',
+                c => c,
+            })
+            .collect();
+        if name.is_empty() || name.starts_with('.') {
+            format!("_{}", name)
+        } else {
+            name
+        }
+    }
+
+    pub fn escape(text: &str) -> String {
+        let mut escaped = String::with_capacity(text.len());
+        for c in text.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&#39;"),
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+}
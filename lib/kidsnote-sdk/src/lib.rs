@@ -1,16 +1,30 @@
+pub mod archive;
 pub mod auth;
+pub mod backup;
 pub mod child;
 pub mod common;
+pub mod export;
 pub mod options;
 pub mod resource;
+pub mod schema;
+pub mod token_store;
 pub mod tool;
 pub mod user;
 
-use auth::KidsnoteAuthSdk;
+use archive::ResponseArchive;
+use auth::{
+    error_types::{AuthError, AuthErrorCode},
+    KidsnoteAuthSdk,
+};
 use child::KidsnoteChildSdk;
+use filetime::FileTime;
 use options::KidsnoteOptions;
-use resource::KidsnoteResourceSdk;
+use resource::{
+    datatypes::{DownloadImageResult, ImageVariant, ReportMediaRef, ResourceImageResponse},
+    KidsnoteResourceSdk,
+};
 use std::sync::{Arc, Mutex};
+use token_store::TokenStore;
 use user::KidsnoteUserSdk;
 
 pub struct KidsnoteSdk {
@@ -26,8 +40,8 @@ impl KidsnoteSdk {
         let config_arc = Arc::new(Mutex::new(config));
         let auth = KidsnoteAuthSdk::new(Arc::clone(&config_arc));
         let resource = KidsnoteResourceSdk::new(Arc::clone(&config_arc));
-        let user: KidsnoteUserSdk = KidsnoteUserSdk::new(Arc::clone(&config_arc));
-        let child = KidsnoteChildSdk::new(Arc::clone(&config_arc));
+        let user: KidsnoteUserSdk = KidsnoteUserSdk::new(Arc::clone(&config_arc), auth.clone());
+        let child = KidsnoteChildSdk::new(Arc::clone(&config_arc), auth.clone());
         KidsnoteSdk {
             options: config_arc,
             auth,
@@ -55,6 +69,11 @@ impl KidsnoteSdk {
         }
     }
 
+    /// 새 토큰을 받으면 바로 저장하도록 저장소 지정
+    pub fn set_token_store(&mut self, token_store: Box<dyn TokenStore>) {
+        self.auth.set_token_store(token_store);
+    }
+
     /// auth sdk
     pub fn auth(&mut self) -> &mut KidsnoteAuthSdk {
         &mut self.auth
@@ -75,13 +94,107 @@ impl KidsnoteSdk {
         &mut self.user
     }
 
-    ///
+    /// API 응답 원문 보관 (내정보, 알림장 목록). None 이면 보관하지 않는다.
+    pub fn set_response_archive(&mut self, archive: Option<ResponseArchive>) {
+        self.user.set_response_archive(archive.clone());
+        self.child.set_response_archive(archive);
+    }
+
+    /// 알림장 이미지 다운로드
+    /// - 이미지 주소가 만료된 경우 알림장을 다시 조회해서 새 주소로 받는다.
+    /// - 원본을 받을 수 없으면 large 이미지로 대신 받는다.
+    /// - fallback_size 는 전에 대신 받은 large 이미지 크기. 같은 크기의 파일이 있으면 받지 않는다.
+    pub async fn download_report_image(
+        &mut self,
+        media: &ReportMediaRef,
+        image: &ResourceImageResponse,
+        file_time: FileTime,
+        download_path: &str,
+        fallback_size: Option<u64>,
+    ) -> Result<DownloadImageResult, AuthError> {
+        let mut image = image.clone();
+        let mut url_refreshed = false;
+
+        if let Some(fallback_size) = fallback_size {
+            let saved_size = std::fs::metadata(download_path).map(|f| f.len()).ok();
+            if saved_size == Some(fallback_size) {
+                let mut result = DownloadImageResult::new(false, 0);
+                result.variant = ImageVariant::Large;
+                return Ok(result);
+            }
+        }
+
+        let mut last_err = match self
+            .resource
+            .download_file(&image.original, image.file_size, file_time, download_path)
+            .await
+        {
+            Ok(bytes) => {
+                return Ok(DownloadImageResult::new(
+                    bytes.is_some(),
+                    bytes.unwrap_or(0),
+                ))
+            }
+            Err(err) => err,
+        };
+
+        if last_err.is_expired_url() {
+            log::warn!(target:"resource", "image url expired. re-resolve. report={}, image={}", media.report_id, image.id);
+            let report = self
+                .child
+                .get_report(media.child_id, media.report_id, media.report_date)
+                .await?;
+            // 새 주소를 찾지 못하면 만료된 large 주소로 받지 않고 실패로 남긴다.
+            let Some(new_image) =
+                report.and_then(|f| f.attached_images.into_iter().find(|f| f.id == image.id))
+            else {
+                return Err(AuthError::ErrorWithCodeString(
+                    AuthErrorCode::NotFound,
+                    format!(
+                        "image not found in report. report={}, image={}",
+                        media.report_id, image.id
+                    ),
+                ));
+            };
+            image = new_image;
+            url_refreshed = true;
+            last_err = match self
+                .resource
+                .download_file(&image.original, image.file_size, file_time, download_path)
+                .await
+            {
+                Ok(bytes) => {
+                    let mut result = DownloadImageResult::new(bytes.is_some(), bytes.unwrap_or(0));
+                    result.url_refreshed = true;
+                    return Ok(result);
+                }
+                Err(err) => err,
+            };
+        }
+
+        if last_err.is_expired_url() || last_err.is_not_found() {
+            log::warn!(target:"resource", "original image unavailable. fallback to large. report={}, image={}, {}", media.report_id, image.id, last_err);
+            // large 이미지는 크기를 알 수 없어 받아둔 파일이 있어도 다시 받는다.
+            let bytes = self
+                .resource
+                .download_file(&image.large, -1, file_time, download_path)
+                .await?;
+            let mut result = DownloadImageResult::new(bytes.is_some(), bytes.unwrap_or(0));
+            result.variant = ImageVariant::Large;
+            result.url_refreshed = url_refreshed;
+            return Ok(result);
+        }
+
+        Err(last_err)
+    }
+
+    /// client id
     pub fn get_client_id(&self) -> String {
         let options = &self.options.lock().unwrap();
         options.get_client_id()
     }
 
-    ///
+    /// api host
     pub fn get_host(&self) -> String {
         let options = &self.options.lock().unwrap();
         options.get_host()
@@ -90,6 +203,12 @@ impl KidsnoteSdk {
 
 //#[cfg(tests)]
 mod tests {
+    mod archive;
+    mod auth;
+    mod backup;
+    mod export;
+    mod schema;
+    mod token_store;
     mod tool;
 
     #[ignore]
@@ -1,3 +1,4 @@
+use chrono::{Duration, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::auth::{
@@ -51,6 +52,10 @@ impl KidsnoteOptions {
         self.host.as_str()
     }
 
+    pub fn get_user_id(&self) -> Option<String> {
+        self.user_id.clone()
+    }
+
     pub fn get_refresh_token(&self) -> Option<String> {
         self.refresh_token.clone()
     }
@@ -71,15 +76,29 @@ impl KidsnoteOptions {
         self.refresh_token = Some(refresh_token);
     }
 
+    /// 로그인, refresh 응답으로 세션 설정
+    /// - 만료 1분 전부터 만료된 것으로 본다.
     pub fn set_session_by_oauth(&mut self, data: OAuthTokenResponse) {
+        let expires_at = Some(data.expires_in)
+            .filter(|f| *f > 0)
+            .map(|f| Utc::now() + Duration::try_seconds(f as i64 - 60).unwrap_or_default());
         self.refresh_token = Some(data.refresh_token);
         self.access_token = Some(KidsnoteAccessToken {
             r#type: data.token_type,
             token: data.access_token,
             expires_in: data.expires_in,
+            expires_at,
         });
     }
 
+    /// access token 이 없거나 만료됨
+    pub fn is_access_token_expired(&self) -> bool {
+        self.access_token.as_ref().is_none_or(|f| {
+            f.expires_at
+                .is_some_and(|expires_at| expires_at <= Utc::now())
+        })
+    }
+
     pub fn remove_session(&mut self) {
         self.refresh_token = None;
         self.access_token = None;
@@ -1,17 +1,77 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::schema::{extra_fields, null_default, ExtraFields, UnknownFields};
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ResourceImageResponse {
     pub id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub access_key: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub original_file_name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub file_size: i32,
+    #[serde(default, deserialize_with = "null_default")]
     pub width: i32,
+    #[serde(default, deserialize_with = "null_default")]
     pub height: i32,
     /// original image url
+    #[serde(default, deserialize_with = "null_default")]
     pub original: String,
     /// large image url
+    #[serde(default, deserialize_with = "null_default")]
     pub large: String,
     /// small image url
+    #[serde(default, deserialize_with = "null_default")]
     pub small: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl UnknownFields for ResourceImageResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+    }
+}
+
+/// 이미지가 첨부된 알림장
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReportMediaRef {
+    pub child_id: u64,
+    pub report_id: u64,
+    pub report_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageVariant {
+    Original,
+    Large,
+}
+
+/// 이미지 다운로드 결과
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DownloadImageResult {
+    /// false 이면 이미 받은 파일이라 건너뜀
+    pub downloaded: bool,
+    pub bytes: u64,
+    pub variant: ImageVariant,
+    /// 만료된 주소를 알림장 재조회로 갱신했는지
+    pub url_refreshed: bool,
+}
+
+impl DownloadImageResult {
+    pub fn new(downloaded: bool, bytes: u64) -> DownloadImageResult {
+        Self {
+            downloaded,
+            bytes,
+            variant: ImageVariant::Original,
+            url_refreshed: false,
+        }
+    }
+
+    pub fn is_fallback(&self) -> bool {
+        self.variant != ImageVariant::Original
+    }
 }
@@ -6,7 +6,10 @@ use std::{
     time::Duration,
 };
 
-use crate::{auth::error_types::AuthError, options::KidsnoteOptions};
+use crate::{
+    auth::error_types::{AuthError, AuthErrorCode},
+    options::KidsnoteOptions,
+};
 
 pub mod datatypes;
 
@@ -28,6 +31,21 @@ impl KidsnoteResourceSdk {
         file_time: FileTime,
         download_path: &str,
     ) -> Result<bool, AuthError> {
+        self.download_file(url, file_size, file_time, download_path)
+            .await
+            .map(|f| f.is_some())
+    }
+
+    /// 파일 다운로드
+    /// - 같은 크기의 파일이 있으면 건너뛰고 None 을 반환한다.
+    /// - 받은 경우 파일 크기를 반환한다.
+    pub async fn download_file(
+        &self,
+        url: &str,
+        file_size: i32,
+        file_time: FileTime,
+        download_path: &str,
+    ) -> Result<Option<u64>, AuthError> {
         // if let Ok(mut ouput_file) = fs::OpenOptions::new()
         //     .append(true)
         //     .create(true)
@@ -43,24 +61,27 @@ impl KidsnoteResourceSdk {
         if let Some(parent_dir) = std::path::Path::new(download_path).parent() {
             if !parent_dir.exists() {
                 fs::create_dir_all(parent_dir).map_err(|err| {
-                    AuthError::GeneralErrorStr(format!("Failed to create directory: {}", err))
+                    AuthError::ErrorWithCodeString(
+                        AuthErrorCode::FileError,
+                        format!("Failed to create directory: {}", err),
+                    )
                 })?;
             }
         }
 
-        match fs::metadata(&download_path) {
+        match fs::metadata(download_path) {
             Ok(metadata) => {
                 if metadata.len() == file_size as u64 {
-                    return Ok(false);
+                    return Ok(None);
                 }
             }
             Err(err) => match err.kind() {
                 std::io::ErrorKind::NotFound => {}
                 _ => {
-                    return Err(AuthError::GeneralErrorStr(format!(
-                        "file metadata error. {:?}",
-                        err
-                    )));
+                    return Err(AuthError::ErrorWithCodeString(
+                        AuthErrorCode::FileError,
+                        format!("file metadata error. {:?}", err),
+                    ));
                 }
             },
         }
@@ -68,36 +89,46 @@ impl KidsnoteResourceSdk {
         for _ in 0..3 {
             let client = crate::common::get_client();
             match client.get(url).timeout(Duration::from_secs(5)).send().await {
+                Ok(response) if !response.status().is_success() => {
+                    let status = response.status();
+                    return Err(AuthError::ErrorWithCodeString(
+                        AuthErrorCode::from_u16(status.as_u16()),
+                        format!("status error. status={:?}", status),
+                    ));
+                }
                 Ok(response) => match response.bytes().await {
                     Ok(bytes) => {
                         let mut output_file = File::create(download_path).map_err(|err| {
-                            AuthError::GeneralErrorStr(format!(
-                                "File open error. path={}, {}",
-                                download_path, err
-                            ))
+                            AuthError::ErrorWithCodeString(
+                                AuthErrorCode::FileError,
+                                format!("File open error. path={}, {}", download_path, err),
+                            )
                         })?;
                         output_file.write_all(&bytes).map_err(|err| {
-                            AuthError::GeneralErrorStr(format!("Error writing to file: {}", err))
+                            AuthError::ErrorWithCodeString(
+                                AuthErrorCode::FileError,
+                                format!("Error writing to file: {}", err),
+                            )
                         })?;
 
                         match filetime::set_file_times(download_path, file_time, file_time) {
                             Ok(()) => {}
                             Err(err) => {
-                                return Err(AuthError::GeneralErrorStr(format!(
-                                    "set_file_times error. {}",
-                                    err
-                                )));
+                                return Err(AuthError::ErrorWithCodeString(
+                                    AuthErrorCode::FileError,
+                                    format!("set_file_times error. {}", err),
+                                ));
                             }
                         }
 
-                        return Ok(true);
+                        return Ok(Some(bytes.len() as u64));
                     }
                     Err(err) => {
                         log::error!("error. {}", err);
-                        return Err(AuthError::GeneralErrorStr(format!(
-                            "unknown error. {:?}",
-                            err
-                        )));
+                        return Err(AuthError::ErrorWithCodeString(
+                            AuthErrorCode::NetworkError,
+                            format!("unknown error. {:?}", err),
+                        ));
                     }
                 },
                 Err(err) => {
@@ -105,8 +136,9 @@ impl KidsnoteResourceSdk {
                 }
             }
         }
-        Err(AuthError::GeneralErrorStr(format!(
-            "unknown error. no call"
-        )))
+        Err(AuthError::ErrorWithCodeStr(
+            AuthErrorCode::NetworkError,
+            "unknown error. no call",
+        ))
     }
 }
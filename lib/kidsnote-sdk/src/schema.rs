@@ -0,0 +1,148 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Mutex,
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::auth::error_types::{AuthError, AuthErrorCode};
+
+/// 모델에 없는 응답 필드
+pub type ExtraFields = BTreeMap<String, Value>;
+
+lazy_static::lazy_static! {
+    /// 이미 로그를 남긴 응답 모양 차이
+    static ref REPORTED: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// null 이나 없는 값은 기본값으로 읽는다.
+pub fn null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// 응답 모델의 모르는 필드 (extra)
+pub trait UnknownFields {
+    /// path 아래에서 모델에 없는 필드의 경로
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>);
+}
+
+impl<T: UnknownFields> UnknownFields for Vec<T> {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        let path = format!("{}[]", path);
+        for item in self {
+            item.unknown_fields(&path, fields);
+        }
+    }
+}
+
+impl<T: UnknownFields> UnknownFields for Option<T> {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        if let Some(item) = self {
+            item.unknown_fields(path, fields);
+        }
+    }
+}
+
+/// extra 의 필드 경로
+pub fn extra_fields(path: &str, extra: &ExtraFields, fields: &mut Vec<String>) {
+    fields.extend(extra.keys().map(|key| format!("{}.{}", path, key)));
+}
+
+/// 응답 읽기
+/// - 읽지 못하면 실패한 위치(JSON 경로)를 오류에 남긴다.
+/// - 모르는 필드, null 이거나 없는데 비어 있지 않은 기본값으로 읽은 필드는 실행 중 한 번만 warn 로그를 남긴다.
+pub fn decode_response<T>(name: &str, body: &[u8]) -> Result<T, AuthError>
+where
+    T: DeserializeOwned + Serialize + UnknownFields,
+{
+    let value: Value = serde_json::from_slice(body).map_err(|err| {
+        AuthError::ErrorWithCodeString(
+            AuthErrorCode::ParseError,
+            format!("parse error. response={}, {}", name, err),
+        )
+    })?;
+    let result: T = serde_path_to_error::deserialize(&value).map_err(|err| {
+        AuthError::ErrorWithCodeString(
+            AuthErrorCode::ParseError,
+            format!(
+                "parse error. response={}, path={}, {}",
+                name,
+                err.path(),
+                err.inner()
+            ),
+        )
+    })?;
+
+    let drift = schema_drift(&value, &result);
+    if !drift.is_empty() {
+        let mut reported = REPORTED.lock().unwrap();
+        for drift in drift {
+            if reported.insert(format!("{}:{}", name, drift)) {
+                log::warn!(target: "schema", "[{}] {}", name, drift);
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// 응답과 모델의 모양 차이
+/// - 배열은 항목 번호 대신 [] 로 묶는다.
+pub fn schema_drift<T>(value: &Value, result: &T) -> Vec<String>
+where
+    T: Serialize + UnknownFields,
+{
+    let mut unknown = Vec::new();
+    result.unknown_fields("$", &mut unknown);
+    let mut drift: Vec<String> = unknown
+        .into_iter()
+        .map(|f| format!("unknown field. path={}", f))
+        .collect();
+    if let Ok(decoded) = serde_json::to_value(result) {
+        defaulted_fields("$", value, &decoded, &mut drift);
+    }
+    drift.sort();
+    drift.dedup();
+    drift
+}
+
+/// null 이거나 없어서 기본값으로 읽은 필드
+/// - Option, #[serde(default)] 처럼 비어 있는 값(null, false, 0, "", [], {})으로 읽은 필드는 빠져도 되는 필드라 제외한다.
+fn defaulted_fields(path: &str, value: &Value, decoded: &Value, drift: &mut Vec<String>) {
+    match (value, decoded) {
+        (Value::Object(value), Value::Object(decoded)) => {
+            for (key, decoded_item) in decoded {
+                let item_path = format!("{}.{}", path, key);
+                match value.get(key) {
+                    None | Some(Value::Null) if is_empty_value(decoded_item) => {}
+                    None => drift.push(format!("missing field. path={}", item_path)),
+                    Some(Value::Null) => drift.push(format!("null field. path={}", item_path)),
+                    Some(item) => defaulted_fields(&item_path, item, decoded_item, drift),
+                }
+            }
+        }
+        (Value::Array(value), Value::Array(decoded)) => {
+            let item_path = format!("{}[]", path);
+            for (item, decoded_item) in value.iter().zip(decoded) {
+                defaulted_fields(&item_path, item, decoded_item, drift);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 기본값으로 만든 빈 값. 객체는 모든 필드가 빈 값이면 빈 값으로 본다.
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(value) => !value,
+        Value::Number(value) => value.as_f64() == Some(0.0),
+        Value::String(value) => value.is_empty(),
+        Value::Array(value) => value.is_empty(),
+        Value::Object(value) => value.values().all(is_empty_value),
+    }
+}
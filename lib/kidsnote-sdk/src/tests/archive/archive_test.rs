@@ -0,0 +1,55 @@
+#[test]
+fn response_archive_test() {
+    use crate::{archive::ResponseArchive, child::datatypes::GetReportsParam};
+
+    let output_dir = std::env::temp_dir().join("kidsnote_response_archive_test");
+    let _ = std::fs::remove_dir_all(&output_dir);
+    let archive = ResponseArchive::new(&output_dir);
+
+    let mut param = GetReportsParam::new();
+    param.date_start = Some(String::from("2023-01-01"));
+    param.date_end = Some(String::from("2023-01-31"));
+    assert_eq!(
+        ResponseArchive::reports_key(&param),
+        "2023-01-01_2023-01-31_1"
+    );
+    param.page = Some(String::from("cD0yMDIz/MDE="));
+    let key = ResponseArchive::reports_key(&param);
+
+    // 모르는 필드도 그대로 남는다.
+    let body = r#"{"count":1,"next":null,"results":[{"id":1,"unknown_field":"😊"}]}"#;
+    let endpoint = ResponseArchive::reports_endpoint(12345);
+    let file_path = archive.save(&endpoint, &key, body.as_bytes()).unwrap();
+    assert_eq!(
+        file_path,
+        output_dir
+            .join("children/12345/reports")
+            .join("2023-01-01_2023-01-31_cD0yMDIz_MDE__fe0170c3.json.gz")
+    );
+    assert_eq!(archive.load(&endpoint, &key).unwrap(), body.as_bytes());
+    assert_eq!(archive.files(&endpoint), vec![file_path]);
+    assert!(archive.files(ResponseArchive::ME_INFO).is_empty());
+
+    // 바꾸거나 자른 key 도 서로 겹치지 않는다.
+    let long_key = "a".repeat(130);
+    let keys = [
+        "p_1".to_string(),
+        "p/1".to_string(),
+        "p=1".to_string(),
+        long_key.clone(),
+        format!("{}b", long_key),
+    ];
+    let file_names: std::collections::HashSet<_> = keys
+        .iter()
+        .map(|f| archive.file_path(&endpoint, f))
+        .collect();
+    assert_eq!(file_names.len(), keys.len());
+    assert_eq!(
+        archive.file_path(&endpoint, "p_1"),
+        output_dir.join("children/12345/reports/p_1.json.gz")
+    );
+    assert!(file_names
+        .iter()
+        .all(|f| f.file_name().unwrap().len() <= 128));
+    let _ = std::fs::remove_dir_all(output_dir);
+}
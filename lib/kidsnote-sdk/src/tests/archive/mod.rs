@@ -0,0 +1 @@
+mod archive_test;
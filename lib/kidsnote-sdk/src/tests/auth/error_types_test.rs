@@ -0,0 +1,40 @@
+#[test]
+fn expired_url_test() {
+    use crate::auth::error_types::{AuthError, AuthErrorCode};
+
+    let err = AuthError::ErrorWithCodeString(
+        AuthErrorCode::from_u16(403),
+        String::from("status error. status=403"),
+    );
+    assert!(err.is_expired_url());
+    assert!(!err.is_not_found());
+
+    let err = AuthError::ErrorWithCode(AuthErrorCode::from_u16(404));
+    assert!(!err.is_expired_url());
+    assert!(err.is_not_found());
+
+    let err = AuthError::GeneralError("unknown error. no call");
+    assert!(!err.is_expired_url());
+    assert!(!err.is_not_found());
+}
+
+#[test]
+fn invalid_credentials_test() {
+    use crate::auth::error_types::{AuthError, AuthErrorCode};
+
+    let err = AuthError::ErrorWithCodeString(
+        AuthErrorCode::from_u16(400),
+        String::from("status error. status=400"),
+    );
+    assert!(err.is_invalid_credentials());
+    assert!(!err.is_server_error());
+
+    let err = AuthError::ErrorWithCode(AuthErrorCode::ServerError);
+    assert!(!err.is_invalid_credentials());
+    assert!(err.is_server_error());
+
+    // 5xx 상태 코드는 서버 오류
+    let err = AuthError::ErrorWithCode(AuthErrorCode::from_u16(503));
+    assert!(err.is_server_error());
+    assert_eq!(AuthErrorCode::from_u16(418), AuthErrorCode::UnknownError);
+}
@@ -0,0 +1,3 @@
+mod error_types_test;
+#[cfg(test)]
+pub(crate) mod refresh_test;
@@ -0,0 +1,199 @@
+use std::sync::{Arc, Mutex};
+
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use crate::{options::KidsnoteOptions, KidsnoteSdk};
+
+const TOKEN_BODY: &str = r#"{"token_type":"Bearer","access_token":"new","scope":"read write","expires_in":3600,"refresh_token":"rotated"}"#;
+
+/// 요청 순서대로 정해둔 응답을 돌려주는 서버. 받은 요청은 "METHOD PATH AUTHORIZATION" 으로 남긴다.
+pub(crate) async fn mock_server(
+    responses: Vec<(u16, &'static str)>,
+) -> (String, Arc<Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let host = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = Arc::clone(&requests);
+    tokio::spawn(async move {
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            let header_end = loop {
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+                if let Some(pos) = buf.windows(4).position(|f| f == b"\r\n\r\n") {
+                    break pos + 4;
+                }
+            };
+            let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+            let header = |name: &str| {
+                head.lines()
+                    .find_map(|f| {
+                        f.split_once(':')
+                            .filter(|(k, _)| k.eq_ignore_ascii_case(name))
+                    })
+                    .map(|(_, v)| v.trim().to_string())
+            };
+            let content_length: usize = header("content-length")
+                .and_then(|f| f.parse().ok())
+                .unwrap_or(0);
+            while buf.len() < header_end + content_length {
+                let n = stream.read(&mut chunk).await.unwrap();
+                buf.extend_from_slice(&chunk[..n]);
+            }
+            let mut request_line = head.lines().next().unwrap_or_default().split(' ');
+            let method = request_line.next().unwrap_or_default();
+            let path = request_line.next().unwrap_or_default();
+            received.lock().unwrap().push(format!(
+                "{} {} {}",
+                method,
+                path.split('?').next().unwrap_or_default(),
+                header("authorization").unwrap_or_default()
+            ));
+            let response = format!(
+                "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            stream.shutdown().await.unwrap();
+        }
+    });
+    (host, requests)
+}
+
+pub(crate) fn session_options(host: String, expires_in: i32) -> KidsnoteOptions {
+    let mut options = KidsnoteOptions::new(None);
+    options.set_host(host);
+    options.set_user_id(String::from("mom"));
+    options.set_session_by_oauth(
+        serde_json::from_value(serde_json::json!({
+            "token_type": "Bearer",
+            "access_token": "old",
+            "expires_in": expires_in,
+            "refresh_token": "refresh_token",
+        }))
+        .unwrap(),
+    );
+    options
+}
+
+#[tokio::test]
+async fn unauthorized_refresh_test() {
+    use crate::token_store::{file::FileTokenStore, TokenStore};
+
+    let me_body = r#"{"user":{"id":1,"username":"mom"},"children":[]}"#;
+    let (host, requests) = mock_server(vec![(401, "{}"), (200, TOKEN_BODY), (200, me_body)]).await;
+    let path = std::env::temp_dir().join(format!(
+        "kidsnote_sdk_refresh_test_{}.toml",
+        std::process::id()
+    ));
+    let mut sdk = KidsnoteSdk::new(session_options(host, 3600));
+    sdk.set_token_store(Box::new(FileTokenStore::new(&path)));
+
+    // 401 이면 refresh 후 새 토큰으로 한 번 더 요청하고, 새 refresh token 을 저장한다.
+    let me = sdk.user().get_myinfo().await.unwrap();
+    assert_eq!(me.user.username, "mom");
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0], "GET /v1/me/info/ Bearer old");
+    assert!(requests[1].starts_with("POST /o/token/"));
+    assert_eq!(requests[2], "GET /v1/me/info/ Bearer new");
+    let token = FileTokenStore::new(&path).load().unwrap().unwrap();
+    assert_eq!(token.refresh_token, "rotated");
+    assert_eq!(token.user_id.as_deref(), Some("mom"));
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test]
+async fn expired_token_refresh_test() {
+    use crate::token_store::memory::MemoryTokenStore;
+
+    let reports_body = r#"{"count":0,"next":null,"previous":null,"results":[]}"#;
+    let (host, requests) = mock_server(vec![(200, TOKEN_BODY), (200, reports_body)]).await;
+    // 만료 1분 전부터 만료로 본다.
+    let mut sdk = KidsnoteSdk::new(session_options(host, 30));
+    sdk.set_token_store(Box::new(MemoryTokenStore::default()));
+
+    // 만료된 토큰은 요청 전에 refresh 한다.
+    let reports = sdk.child().get_reports(3, None).await.unwrap();
+    assert!(reports.results.is_empty());
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].starts_with("POST /o/token/"));
+    assert_eq!(requests[1], "GET /v1_2/children/3/reports/ Bearer new");
+    assert!(!sdk.get_options_clone().is_access_token_expired());
+}
+
+#[tokio::test]
+async fn token_store_save_fail_test() {
+    use crate::{
+        auth::error_types::AuthError,
+        token_store::{datatypes::StoredToken, TokenStore},
+    };
+
+    struct FailTokenStore;
+    impl TokenStore for FailTokenStore {
+        fn load(&mut self) -> Result<Option<StoredToken>, AuthError> {
+            Ok(None)
+        }
+        fn save(&mut self, _token: &StoredToken) -> Result<(), AuthError> {
+            Err(AuthError::GeneralErrorStr(String::from("no passphrase")))
+        }
+        fn clear(&mut self) -> Result<(), AuthError> {
+            Ok(())
+        }
+    }
+
+    let (host, _requests) = mock_server(vec![(200, TOKEN_BODY)]).await;
+    let mut sdk = KidsnoteSdk::new(session_options(host, 3600));
+    sdk.set_token_store(Box::new(FailTokenStore));
+
+    // 새 refresh token 을 저장하지 못하면 로그인도 실패로 돌려준다.
+    let err = sdk.auth().refresh_token("refresh_token").await.unwrap_err();
+    assert!(err.to_string().contains("no passphrase"));
+}
+
+#[tokio::test]
+async fn comments_refresh_test() {
+    use crate::{archive::ResponseArchive, token_store::memory::MemoryTokenStore};
+
+    let page_1 = r#"{"count":2,"next":"2","previous":null,"results":[{"id":1,"created":"2023-01-10T03:30:00Z","author_name":"엄마","content":"감사합니다"}]}"#;
+    let page_2 = r#"{"count":2,"next":null,"previous":"1","results":[{"id":2,"created":"2023-01-10T04:00:00Z","author":{"name":"선생님"},"content":"네~"}]}"#;
+    let (host, requests) = mock_server(vec![
+        (200, page_1),
+        (401, "{}"),
+        (200, TOKEN_BODY),
+        (200, page_2),
+    ])
+    .await;
+    let archive_dir =
+        std::env::temp_dir().join(format!("kidsnote_sdk_comments_test_{}", std::process::id()));
+    let mut sdk = KidsnoteSdk::new(session_options(host, 3600));
+    sdk.set_token_store(Box::new(MemoryTokenStore::default()));
+    sdk.set_response_archive(Some(ResponseArchive::new(&archive_dir)));
+
+    // 다음 페이지까지 받고, 401 이면 refresh 후 같은 페이지를 다시 요청한다.
+    let comments = sdk.child().get_report_comments(12345).await.unwrap();
+    assert_eq!(comments.len(), 2);
+    assert_eq!(comments[0].writer_name(), "엄마");
+    assert_eq!(comments[1].writer_name(), "선생님");
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests[0], "GET /v1/reports/12345/comments/ Bearer old");
+    assert_eq!(requests[3], "GET /v1/reports/12345/comments/ Bearer new");
+
+    // 받은 페이지는 응답 보관 파일로 남는다.
+    let archive = ResponseArchive::new(&archive_dir);
+    assert_eq!(
+        archive
+            .files(&ResponseArchive::comments_endpoint(12345))
+            .len(),
+        2
+    );
+    std::fs::remove_dir_all(&archive_dir).unwrap();
+}
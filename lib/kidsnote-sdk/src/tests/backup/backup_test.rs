@@ -0,0 +1,179 @@
+#[test]
+fn report_record_test() {
+    use std::collections::HashMap;
+
+    use crate::{
+        backup::{
+            datatypes::{BackupLayout, BackupReportRecord, BackupSink, BackupSource},
+            event::BackupEvent,
+            BackupJob,
+        },
+        child::datatypes::ChildReportDataResponse,
+        tool::render_theme::TextOptions,
+        user::datatypes::MeInfoChildrenResponse,
+    };
+
+    let content =
+        "  오늘은   눈이 와서\r\n눈사람을 만들었어요. 😊  \n\n\t내일은 장갑을 보내주세요.";
+    let report: ChildReportDataResponse = serde_json::from_value(serde_json::json!({
+        "id": 12345,
+        "created": "2023-01-10T01:00:00Z",
+        "modified": "2023-01-10T01:00:00Z",
+        "date_written": "2023-01-10",
+        "author": { "id": 1, "type": "teacher", "name": "홍길동", "picture": null, "username": "teacher" },
+        "author_name": "홍길동 선생님",
+        "center": 1,
+        "cls": 2,
+        "class_name": "해바라기반",
+        "child": 3,
+        "child_name": "홍아기",
+        "child_picture": null,
+        "is_sent_from_center": false,
+        "content": content,
+        "weather": "snowy",
+        "num_comments": 0,
+        "read_by_me": true,
+        "read_by_parent": { "date_read": "" },
+        "attached_images": [],
+        "thumbnail": null
+    }))
+    .unwrap();
+    let child: MeInfoChildrenResponse = serde_json::from_value(serde_json::json!({
+        "id": 3,
+        "created": "2019-06-01T00:00:00Z",
+        "name": "홍아기",
+        "date_birth": "2019-05-20",
+        "gender": "M",
+        "family_type": "mother",
+        "picture": null,
+        "parent": { "id": 4, "type": "parent", "name": "홍엄마", "picture": null, "username": "mom" },
+        "enrollment": []
+    }))
+    .unwrap();
+    let center_map = HashMap::from([(1, String::from("키즈노트어린이집"))]);
+    let source = BackupSource::from_report(report, &child, &center_map);
+
+    // 원문은 정리하지 않고 그대로 저장한다.
+    let output_dir = std::env::temp_dir().join("kidsnote_report_record_test");
+    let layout = BackupLayout::new(output_dir.to_str().unwrap());
+    let record_file = layout.record_file(&source);
+    source.record().unwrap().save(&record_file).unwrap();
+    let record = BackupReportRecord::from_file(&record_file).unwrap();
+    assert_eq!(record.report.content.as_deref(), Some(content));
+    assert_eq!(record.center_name.as_deref(), Some("키즈노트어린이집"));
+    assert_eq!(record.child_date_birth.as_deref(), Some("2019-05-20"));
+
+    // .txt, 이미지에 쓰는 내용만 정리한다.
+    assert_eq!(
+        TextOptions::default().normalize(content),
+        vec![
+            "오늘은 눈이 와서",
+            "눈사람을 만들었어요. 😊",
+            "",
+            "내일은 장갑을 보내주세요."
+        ]
+    );
+    let options = TextOptions {
+        collapse_spaces: false,
+        trim_lines: false,
+        txt_wrap: 0,
+    };
+    assert_eq!(options.normalize(content)[0], "  오늘은   눈이 와서");
+
+    // 서버에 요청하지 않고 저장된 원문으로 다시 만든다.
+    let local = layout.local_sources(None);
+    assert!(local.errors.is_empty());
+    assert_eq!(local.sources.len(), 1);
+    assert_eq!(local.sources[0].report_content.as_deref(), Some(content));
+    let job = BackupJob::builder().layout(layout.clone()).build();
+    let mut saved = Vec::new();
+    job.render(
+        &mut |event: &BackupEvent| {
+            if let BackupEvent::ContentSaved { path, .. } = event {
+                saved.push(path.clone());
+            }
+        },
+        local.sources,
+    );
+    assert_eq!(saved.len(), 2);
+    assert!(layout.text_file(&source).exists());
+
+    // 카드 쪽 수가 줄면 남은 이전 쪽 파일을 지운다.
+    let format = crate::tool::render_theme::RenderTheme::default().format;
+    for page in 1..=3 {
+        std::fs::write(layout.card_file(&source, format, Some(page)), b"old").unwrap();
+    }
+    let job = BackupJob::builder()
+        .layout(layout.clone())
+        .sink(BackupSink::ReportCard)
+        .build();
+    job.render(
+        &mut |_: &BackupEvent| {},
+        layout.local_sources(None).sources,
+    );
+    assert!(layout.card_file(&source, format, None).exists());
+    assert!((1..=3).all(|page| !layout.card_file(&source, format, Some(page)).exists()));
+    let _ = std::fs::remove_dir_all(output_dir);
+}
+
+#[tokio::test]
+async fn child_synced_test() {
+    use crate::{
+        backup::{event::BackupEvent, BackupJob},
+        tests::auth::refresh_test::{mock_server, session_options},
+        token_store::memory::MemoryTokenStore,
+        user::datatypes::MeInfoChildrenResponse,
+        KidsnoteSdk,
+    };
+
+    let reports_body = r#"{"count":0,"next":null,"previous":null,"results":[]}"#;
+    let (host, _) = mock_server(vec![(200, reports_body); 4]).await;
+    let mut sdk = KidsnoteSdk::new(session_options(host, 3600));
+    sdk.set_token_store(Box::new(MemoryTokenStore::default()));
+    let child: MeInfoChildrenResponse = serde_json::from_value(serde_json::json!({
+        "id": 3,
+        "created": "2019-06-01T00:00:00Z",
+        "name": "홍아기",
+        "date_birth": "2019-05-20",
+        "gender": "M",
+        "family_type": "mother",
+        "picture": null,
+        "parent": { "id": 4, "type": "parent", "name": "홍엄마", "picture": null, "username": "mom" },
+        "enrollment": []
+    }))
+    .unwrap();
+    let output_dir = std::env::temp_dir().join("kidsnote_child_synced_test");
+
+    // 빠짐없이 조회한 경우만 synced
+    let cases = [
+        (false, None, false, true),
+        (true, None, false, false),
+        (false, Some("2023-01-10"), false, false),
+        (false, Some("2023-01-10"), true, true),
+    ];
+    for (dry_run, date_start, incremental, expected) in cases {
+        let mut job = BackupJob::builder()
+            .output_dir(output_dir.to_str().unwrap())
+            .children(vec![child.clone()])
+            .date_start(date_start.map(String::from))
+            .dry_run(dry_run)
+            .incremental(incremental)
+            .build();
+        let mut synced = None;
+        job.run(&mut sdk, &mut |event: &BackupEvent| {
+            if let BackupEvent::ChildFinished { synced: f, .. } = event {
+                synced = Some(*f);
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            synced,
+            Some(expected),
+            "dry_run={}, date_start={:?}",
+            dry_run,
+            date_start
+        );
+    }
+    let _ = std::fs::remove_dir_all(output_dir);
+}
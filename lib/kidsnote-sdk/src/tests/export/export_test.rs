@@ -0,0 +1,88 @@
+#[test]
+fn html_export_test() {
+    use crate::{
+        backup::datatypes::{BackupLayout, BackupReportRecord, BackupSource},
+        child::datatypes::ChildReportDataResponse,
+        export::HtmlExport,
+    };
+
+    let report: ChildReportDataResponse = serde_json::from_value(serde_json::json!({
+        "id": 12345,
+        "created": "2023-01-10T01:00:00Z",
+        "date_written": "2023-01-10",
+        "author_name": "홍길동 선생님",
+        "class_name": "해바라기반",
+        "child": 3,
+        "child_name": "홍아기",
+        "content": "오늘은 <눈>이 와서\n눈사람을 만들었어요.",
+        "weather": "snowy",
+        "num_comments": 2,
+        "attached_images": [
+            { "id": 1, "original_file_name": "a.jpg" },
+            { "id": 2, "original_file_name": "b.jpg" }
+        ]
+    }))
+    .unwrap();
+    let source = BackupSource::from_record(BackupReportRecord {
+        child_id: 3,
+        child_name: String::from("홍아기"),
+        child_date_birth: None,
+        center_name: Some(String::from("키즈노트어린이집")),
+        saved_at: chrono::Utc::now(),
+        report,
+        // 댓글 2개 중 1개만 받은 상태
+        comments: serde_json::from_value(serde_json::json!([{
+            "id": 7,
+            "created": "2023-01-10T03:30:00Z",
+            "author": { "name": "홍아기 엄마" },
+            "content": "감사합니다 <3"
+        }]))
+        .unwrap(),
+    });
+
+    let output_dir = std::env::temp_dir().join("kidsnote_html_export_test");
+    let _ = std::fs::remove_dir_all(&output_dir);
+    let layout = BackupLayout::new(output_dir.to_str().unwrap());
+    // 첫 번째 사진만 받은 상태
+    let media_file = layout.media_file(&source, &source.attached_images[0]);
+    std::fs::create_dir_all(media_file.parent().unwrap()).unwrap();
+    image::RgbImage::from_pixel(320, 200, image::Rgb([200, 120, 40]))
+        .save(&media_file)
+        .unwrap();
+
+    let export_dir = output_dir.join("html");
+    let result = HtmlExport::new(layout, &export_dir)
+        .thumbnail_size(64)
+        .export(std::slice::from_ref(&source));
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    assert_eq!((result.children, result.reports), (1, 1));
+    assert_eq!((result.photos, result.photos_missing), (1, 1));
+    assert_eq!(result.pages, 3);
+
+    let child_dir = export_dir.join("홍아기");
+    let timeline = std::fs::read_to_string(child_dir.join("index.html")).unwrap();
+    assert!(timeline.contains("2023년") && timeline.contains("1월 10일 (화)"));
+    let page = std::fs::read_to_string(child_dir.join("reports/20230110_12345.html")).unwrap();
+    assert!(page.contains("오늘은 &lt;눈&gt;이 와서"));
+    assert!(page.contains("<dd>눈</dd>"));
+    assert!(page.contains("댓글 2개"));
+    assert!(page.contains("<span class=\"writer\">홍아기 엄마</span>"));
+    assert!(page.contains("2023-01-10 12:30"));
+    assert!(page.contains("감사합니다 &lt;3"));
+    assert!(page.contains("받지 않은 댓글 1개"));
+    assert!(page.contains("id=\"photo-1\""));
+    assert!(export_dir.join("style.css").exists());
+    let thumb_file = "20230110_홍아기_알림장_12345_1.jpg.jpg";
+    let thumb = image::open(child_dir.join("thumbs/64").join(thumb_file)).unwrap();
+    assert_eq!((thumb.width(), thumb.height()), (64, 64));
+
+    // 크기를 바꾸면 작은 사진을 새로 만든다.
+    let result = HtmlExport::new(BackupLayout::new(output_dir.to_str().unwrap()), &export_dir)
+        .thumbnail_size(32)
+        .export(&[source]);
+    assert!(result.errors.is_empty(), "{:?}", result.errors);
+    let thumb = image::open(child_dir.join("thumbs/32").join(thumb_file)).unwrap();
+    assert_eq!((thumb.width(), thumb.height()), (32, 32));
+    let page = std::fs::read_to_string(child_dir.join("reports/20230110_12345.html")).unwrap();
+    assert!(page.contains(&format!("thumbs/32/{}", thumb_file)));
+}
@@ -0,0 +1 @@
+mod export_test;
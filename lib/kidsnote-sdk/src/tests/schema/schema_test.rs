@@ -0,0 +1,95 @@
+#[test]
+fn tolerant_response_test() {
+    use crate::{
+        schema::{decode_response, schema_drift},
+        user::datatypes::MeInfoResponse,
+    };
+
+    let mut value = serde_json::json!({
+        "user": {
+            "id": 1,
+            "username": "mom",
+            "type": "parent",
+            "name": "홍엄마",
+            "country_code": "82",
+            "phone": null,
+            "email": null,
+            "picture": null,
+            "date_joined": "2019-06-01",
+            "subscription": false,
+            "subscription_updated_at": null,
+            "third_party_consent": false,
+            "date_store_allowed": null,
+            "is_staff": false,
+            "show_change_password": true,
+            "use_privacy": false,
+            "new_flag": true
+        },
+        "children": [{
+            "id": 3,
+            "created": "2019-06-01",
+            "name": "홍아기",
+            "date_birth": "2019-05-20",
+            "gender": "M",
+            "family_type": "mother",
+            "picture": null,
+            "parent": null,
+            "enrollment": []
+        }]
+    });
+
+    // null, 없는 필드는 기본값으로 읽고 모르는 필드는 extra 에 남긴다.
+    let body = serde_json::to_vec(&value).unwrap();
+    let me: MeInfoResponse = decode_response("me_info", &body).unwrap();
+    assert_eq!(me.user.phone, "");
+    assert_eq!(me.user.description, "");
+    assert_eq!(me.user.extra["new_flag"], serde_json::json!(true));
+    assert_eq!(me.children[0].parent.id, 0);
+    // 빠져도 되는 필드(Option, 기본값)가 null 이거나 없는 건 모양 차이로 보지 않는다.
+    assert_eq!(
+        schema_drift(&value, &me),
+        vec!["unknown field. path=$.user.new_flag"]
+    );
+
+    // 읽을 수 없으면 실패한 위치를 알려준다.
+    value["children"][0]["id"] = serde_json::json!("3");
+    let body = serde_json::to_vec(&value).unwrap();
+    let err = decode_response::<MeInfoResponse>("me_info", &body).unwrap_err();
+    assert!(err.to_string().contains("path=children[0].id"), "{}", err);
+}
+
+#[test]
+fn defaulted_field_test() {
+    use serde::{Deserialize, Serialize};
+
+    use crate::schema::{schema_drift, UnknownFields};
+
+    fn first_page() -> u32 {
+        1
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct Page {
+        #[serde(default = "first_page")]
+        page: u32,
+        #[serde(default)]
+        count: u32,
+        name: Option<String>,
+    }
+
+    impl UnknownFields for Page {
+        fn unknown_fields(&self, _path: &str, _fields: &mut Vec<String>) {}
+    }
+
+    // 비어 있지 않은 기본값으로 채운 필드만 알려준다.
+    let value = serde_json::json!({ "name": null });
+    let page: Page = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!(
+        schema_drift(&value, &page),
+        vec!["missing field. path=$.page"]
+    );
+
+    let value = serde_json::json!({ "page": 2, "count": 3, "name": "a" });
+    let page: Page = serde_json::from_value(value.clone()).unwrap();
+    assert!(schema_drift(&value, &page).is_empty());
+}
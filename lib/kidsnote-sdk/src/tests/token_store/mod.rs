@@ -0,0 +1 @@
+mod token_store_test;
\ No newline at end of file
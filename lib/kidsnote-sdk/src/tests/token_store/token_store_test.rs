@@ -0,0 +1,55 @@
+#[test]
+fn file_token_store_test() {
+    use crate::token_store::{datatypes::StoredToken, file::FileTokenStore, TokenStore};
+
+    let path = std::env::temp_dir().join(format!(
+        "kidsnote_sdk_file_token_store_test_{}.toml",
+        std::process::id()
+    ));
+    let mut token_store = FileTokenStore::new(&path);
+    assert!(token_store.load().unwrap().is_none());
+
+    // 새 토큰을 받으면 덮어쓴다.
+    for refresh_token in ["refresh_token", "rotated"] {
+        token_store
+            .save(&StoredToken {
+                user_id: Some(String::from("user")),
+                refresh_token: String::from(refresh_token),
+            })
+            .unwrap();
+    }
+    let token = FileTokenStore::new(&path).load().unwrap().unwrap();
+    assert_eq!(token.user_id.as_deref(), Some("user"));
+    assert_eq!(token.refresh_token, "rotated");
+
+    token_store.clear().unwrap();
+    assert!(token_store.load().unwrap().is_none());
+}
+
+#[test]
+fn encrypted_file_token_store_test() {
+    use crate::token_store::{datatypes::StoredToken, file::EncryptedFileTokenStore, TokenStore};
+
+    let path = std::env::temp_dir().join(format!(
+        "kidsnote_sdk_encrypted_token_store_test_{}.toml",
+        std::process::id()
+    ));
+    let mut token_store = EncryptedFileTokenStore::new(&path, "passphrase");
+    token_store
+        .save(&StoredToken {
+            user_id: Some(String::from("user")),
+            refresh_token: String::from("refresh_token"),
+        })
+        .unwrap();
+
+    // 평문으로 저장되지 않는다.
+    let toml_string = std::fs::read_to_string(&path).unwrap();
+    assert!(!toml_string.contains("\"refresh_token\""));
+
+    let token = token_store.load().unwrap().unwrap();
+    assert_eq!(token.refresh_token, "refresh_token");
+    assert!(EncryptedFileTokenStore::new(&path, "wrong").load().is_err());
+
+    token_store.clear().unwrap();
+    assert!(token_store.load().unwrap().is_none());
+}
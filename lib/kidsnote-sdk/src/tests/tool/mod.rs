@@ -1 +1,5 @@
-mod image_tool_test;
\ No newline at end of file
+#[allow(clippy::module_inception, clippy::println_empty_string)]
+mod image_tool_test;
+mod render_theme_test;
+mod report_header_test;
+mod text_layout_test;
@@ -0,0 +1,119 @@
+#[test]
+fn render_preset_test() {
+    use crate::tool::{
+        image_tool::{ImageTool, RenderReport},
+        render_theme::{RenderFormat, RenderTheme},
+    };
+
+    let output_dir = std::env::temp_dir().join("kidsnote_render_preset_test");
+    let report = RenderReport {
+        title: String::from("제목 : 2023년 1월 1일 알림장"),
+        center_name: Some(String::from("키즈노트어린이집")),
+        author_name: String::from("홍길동 선생님"),
+        contents: vec![
+            String::from("오늘은 친구들과 함께 놀이터에서 신나게 뛰어 놀았어요."),
+            String::new(),
+            String::from("내일은 체육복을 입혀 보내주세요."),
+        ],
+        report_id: Some(12345),
+        report_date: chrono::Utc::now(),
+        weather: None,
+        class_name: None,
+        child_date_birth: None,
+        read_at: None,
+    };
+
+    for name in RenderTheme::PRESETS {
+        let theme = RenderTheme::preset(name).unwrap();
+        theme.validate().unwrap();
+
+        let file_path = output_dir.join(format!("{}.{}", name, theme.format.extension()));
+        let file_path = file_path.to_str().unwrap();
+        ImageTool::render_report_file(&report, &theme, file_path).unwrap();
+
+        let format = image::ImageFormat::from_path(file_path).unwrap();
+        let (width, _height) = image::image_dimensions(file_path).unwrap();
+        assert!(width <= theme.page_width, "{} width={}", name, width);
+        if theme.fixed_width {
+            assert_eq!(width, theme.page_width);
+        }
+        match theme.format {
+            RenderFormat::Jpeg => assert_eq!(format, image::ImageFormat::Jpeg),
+            RenderFormat::Png => assert_eq!(format, image::ImageFormat::Png),
+            RenderFormat::Webp => assert_eq!(format, image::ImageFormat::WebP),
+        }
+    }
+
+    // WebP 저장
+    let theme = RenderTheme {
+        format: RenderFormat::Webp,
+        ..RenderTheme::default()
+    };
+    let file_path = output_dir.join("default.webp");
+    let file_path = file_path.to_str().unwrap();
+    ImageTool::render_report_file(&report, &theme, file_path).unwrap();
+    assert!(image::open(file_path).is_ok());
+
+    let theme = RenderTheme {
+        background: String::from("white"),
+        ..RenderTheme::default()
+    };
+    assert!(theme.validate().is_err());
+    let _ = std::fs::remove_dir_all(output_dir);
+}
+
+#[test]
+fn report_card_test() {
+    use crate::tool::{
+        image_tool::{ImageTool, RenderReport},
+        render_theme::RenderTheme,
+    };
+
+    let output_dir = std::env::temp_dir().join("kidsnote_report_card_test");
+    std::fs::create_dir_all(&output_dir).unwrap();
+    let mut photo_files = Vec::new();
+    for index in 0..7u8 {
+        let photo = image::RgbImage::from_pixel(
+            400 + index as u32 * 50,
+            300,
+            image::Rgb([index * 30, 100, 200]),
+        );
+        let file_path = output_dir.join(format!("photo_{}.png", index));
+        photo.save(&file_path).unwrap();
+        photo_files.push(file_path.to_str().unwrap().to_string());
+    }
+    // 읽을 수 없는 사진은 건너뛴다.
+    photo_files.push(output_dir.join("missing.jpg").to_str().unwrap().to_string());
+
+    let report = RenderReport {
+        title: String::from("제목 : 2023년 1월 1일 알림장"),
+        center_name: None,
+        author_name: String::from("홍길동 선생님"),
+        contents: vec![
+            String::from("오늘은 친구들과 함께 놀이터에서 신나게 뛰어 놀았어요.");
+            20
+        ],
+        report_id: Some(12345),
+        report_date: chrono::Utc::now(),
+        weather: Some(String::from("sunny")),
+        class_name: None,
+        child_date_birth: None,
+        read_at: None,
+    };
+    assert_eq!(report.weather_line().unwrap(), "날씨 : 맑음");
+
+    let mut theme = RenderTheme::preset("card").unwrap();
+    theme.report_card.max_height = None;
+    let pages = ImageTool::render_report_card(&report, &photo_files, &theme).unwrap();
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0].width(), theme.page_width);
+
+    // 긴 알림장은 여러 장으로 나눈다.
+    theme.report_card.max_height = Some(800);
+    let pages = ImageTool::render_report_card(&report, &photo_files, &theme).unwrap();
+    assert!(pages.len() > 1);
+    for page in &pages {
+        assert!(page.height() <= 800, "height={}", page.height());
+    }
+    let _ = std::fs::remove_dir_all(output_dir);
+}
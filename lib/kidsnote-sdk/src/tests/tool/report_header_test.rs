@@ -0,0 +1,66 @@
+#[test]
+fn report_header_test() {
+    use crate::tool::{
+        image_tool::{ImageTool, RenderReport},
+        render_theme::{RenderTheme, ReportHeaderOptions},
+    };
+    use chrono::TimeZone;
+
+    let report = RenderReport {
+        title: String::from("제목 : 2023년 1월 10일 알림장"),
+        center_name: None,
+        author_name: String::from("홍길동 선생님"),
+        contents: vec![String::from("오늘은 눈이 와서 눈사람을 만들었어요.")],
+        report_id: Some(12345),
+        // 2023-01-10 08:00 (Asia/Seoul)
+        report_date: chrono::Utc.with_ymd_and_hms(2023, 1, 9, 23, 0, 0).unwrap(),
+        weather: Some(String::from("snowy")),
+        class_name: Some(String::from("해바라기반")),
+        child_date_birth: Some(String::from("2019-05-20")),
+        read_at: Some(String::from("2023-01-10T09:30:00.123456Z")),
+    };
+    assert_eq!(report.child_age(), Some((3, 7)));
+    assert_eq!(
+        report.age_line(true).unwrap(),
+        "나이 : 3세 7개월 (한국 나이 5세)"
+    );
+
+    let header = ReportHeaderOptions {
+        class_name: true,
+        weather: true,
+        age: true,
+        korean_age: false,
+        read_at: true,
+    };
+    assert_eq!(
+        report.header_lines(&header),
+        vec![
+            "반 : 해바라기반",
+            "날씨 : 눈",
+            "나이 : 3세 7개월",
+            "읽음 : 2023-01-10 18:30"
+        ]
+    );
+    assert!(report
+        .header_lines(&ReportHeaderOptions::default())
+        .is_empty());
+
+    let output_dir = std::env::temp_dir().join("kidsnote_report_header_test");
+    let file_path = output_dir.join("report.txt");
+    let file_path = file_path.to_str().unwrap();
+    let theme = RenderTheme {
+        header,
+        ..RenderTheme::default()
+    };
+    ImageTool::render_report_txt_file(&report, &theme, file_path).unwrap();
+    let text = std::fs::read_to_string(file_path).unwrap();
+    assert!(text
+        .starts_with("제목 : 2023년 1월 10일 알림장\n작성자 : 홍길동 선생님\n반 : 해바라기반\n"));
+    assert!(text.contains("읽음 : 2023-01-10 18:30\n---\n"));
+
+    // 이미지는 작성자 아래 정보만큼 길어진다.
+    let without_header = ImageTool::render_report(&report, &RenderTheme::default()).unwrap();
+    let with_header = ImageTool::render_report(&report, &theme).unwrap();
+    assert!(with_header.height() > without_header.height());
+    let _ = std::fs::remove_dir_all(output_dir);
+}
@@ -0,0 +1,136 @@
+#[test]
+fn wrap_by_width_test() {
+    use crate::tool::text_layout::TextLayout;
+    use rusttype::{Font, Scale};
+
+    let font_bytes = include_bytes!("../../../fonts/NanumSquareL.ttf");
+    let font = Font::try_from_bytes(font_bytes as &[u8]).unwrap();
+    let layout = TextLayout::new(&font, Scale::uniform(24.0));
+
+    let text = "ㅠㅠ 오늘은 꼭 등원하려하는데.. 이제가 어제 못자서 그런지 안일어나네요.. 오후에 가도 괜찮은걸까요..?";
+    let lines = layout.wrap(text, 300.0);
+    assert!(lines.len() > 1);
+    assert!(lines.iter().all(|f| layout.text_width(f) <= 300.0));
+    // 띄어쓰기에서 나누므로 글자가 빠지지 않는다.
+    assert_eq!(lines.join(" "), text);
+
+    // 띄어쓰기 없이 긴 문장은 음절 단위로 나눈다.
+    let text =
+        "ㅠㅠ오늘은꼭등원하려하는데..이제가어제못자서그런지안일어나네요..오후에가도괜찮은걸까요..?";
+    let lines = layout.wrap(text, 300.0);
+    assert!(lines.len() > 1);
+    assert!(lines.iter().all(|f| layout.text_width(f) <= 300.0));
+    assert_eq!(lines.concat(), text);
+
+    // 줄바꿈과 빈 줄 유지
+    let lines = layout.wrap("첫째 줄\n\n셋째 줄", 300.0);
+    assert_eq!(lines, vec!["첫째 줄", "", "셋째 줄"]);
+
+    // 한글 한 글자는 영문보다 넓다.
+    assert!(layout.text_width("가나다") > layout.text_width("abc"));
+}
+
+#[test]
+fn text_to_image_size_test() {
+    let output_dir = std::env::temp_dir().join("kidsnote_text_to_image_size_test");
+    let file_path = output_dir.join("report.png");
+    let file_path = file_path.to_str().unwrap();
+
+    let long_text = "오늘은 친구들과 함께 놀이터에서 신나게 뛰어 놀았어요. ".repeat(10);
+    let contents = vec!["짧은 줄", long_text.as_str()];
+    crate::tool::image_tool::ImageTool::text_to_image_file(
+        "제목 : 2023년 1월 1일 알림장",
+        &None,
+        "홍길동 엄마",
+        &contents,
+        file_path,
+        chrono::Utc::now(),
+    )
+    .unwrap();
+
+    // 긴 줄은 나눠지고, 이미지 폭은 최대 폭을 넘지 않는다.
+    let (width, height) = image::image_dimensions(file_path).unwrap();
+    assert!(width <= 760, "width={}", width);
+    assert!(height > 36 * 5, "height={}", height);
+    let _ = std::fs::remove_dir_all(output_dir);
+}
+
+#[test]
+fn fallback_font_test() {
+    use crate::tool::{render_theme::RenderTheme, text_layout::TextLayout};
+    use rusttype::Scale;
+
+    let samples = [
+        "오늘 날씨가 좋아서 산책 다녀왔어요 ☀️ 친구들이랑 즐겁게 놀았답니다 😊",
+        "감사합니다 ❤️ 선생님 최고예요 👍🏻 ♥♡ ★☆ ✔",
+        "일본어 : ありがとう, 영어 : Thank you!",
+        "도시락 잘 먹었어요~^^ ㅎㅎ (o^^o) ✌ ☺",
+        "생일 축하해요 🎉 고맙습니다 🙏 사랑해요 💕 👍",
+    ];
+    let theme = RenderTheme::default();
+    let font = theme.body.font();
+    let fallback_fonts = theme.load_fallback_fonts().unwrap();
+    let layout = TextLayout::new(&font, Scale::uniform(24.0)).fallback_fonts(&fallback_fonts);
+
+    // 기본 글꼴만으로는 그릴 수 없는 글자가 있다.
+    assert!(samples
+        .concat()
+        .chars()
+        .any(|c| !TextLayout::has_glyph(&font, c)));
+
+    // 모든 글자가 .notdef(0) 가 아닌, 모양이 있는 글리프로 그려지고 U+FFFD 로 바뀌지 않는다.
+    for text in samples {
+        for (run_font, run) in layout.runs(text) {
+            assert!(!run.contains('\u{FFFD}'), "replaced. text={}", text);
+            for c in run.chars() {
+                assert_ne!(
+                    run_font.glyph(c).id().0,
+                    0,
+                    "notdef. text={}, char={:?}",
+                    text,
+                    c
+                );
+                assert!(
+                    TextLayout::has_glyph(run_font, c),
+                    "empty glyph. char={:?}",
+                    c
+                );
+            }
+        }
+        assert!(layout.text_width(text) > 0.0);
+    }
+
+    // 이모티콘은 내장 기호 글꼴로, 기호 글꼴에 없는 이모지는 내장 이모지 글꼴로 그린다.
+    let runs = layout.runs("좋아요😊");
+    assert_eq!(runs.len(), 2);
+    assert_eq!(runs[1].1, "😊");
+    for c in ['👍', '🎉', '🙏', '💕'] {
+        let runs = layout.runs(&c.to_string());
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].1, c.to_string(), "replaced. char={:?}", c);
+        assert!(
+            TextLayout::has_glyph(runs[0].0, c),
+            "empty glyph. char={:?}",
+            c
+        );
+    }
+
+    // 내장 글꼴에 없는 한자는 fallback_fonts 에 CJK 글꼴을 지정해야 그린다.
+    let runs = layout.runs("漢");
+    assert_eq!(runs[0].1, "\u{FFFD}");
+
+    // 설정한 글꼴 파일을 먼저 찾는다.
+    let theme = RenderTheme {
+        fallback_fonts: vec![format!(
+            "{}/fonts/DejaVuSans.ttf",
+            env!("CARGO_MANIFEST_DIR")
+        )],
+        ..RenderTheme::default()
+    };
+    assert_eq!(theme.load_fallback_fonts().unwrap().len(), 3);
+    let theme = RenderTheme {
+        fallback_fonts: vec![String::from("./not_found.ttf")],
+        ..RenderTheme::default()
+    };
+    assert!(theme.load_fallback_fonts().is_err());
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// 저장할 로그인 정보
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct StoredToken {
+    pub user_id: Option<String>,
+    pub refresh_token: String,
+}
+
+/// 암호화해서 저장하는 로그인 정보
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EncryptedStoredToken {
+    pub user_id: Option<String>,
+    pub encrypted_refresh_token: String,
+}
@@ -0,0 +1,144 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::auth::error_types::AuthError;
+
+use super::{
+    datatypes::{EncryptedStoredToken, StoredToken},
+    secret, TokenStore,
+};
+
+/// TOML 파일에 평문으로 저장
+#[derive(Debug, Clone)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    pub fn new<P: AsRef<Path>>(path: P) -> FileTokenStore {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+}
+
+impl TokenStore for FileTokenStore {
+    fn load(&mut self) -> Result<Option<StoredToken>, AuthError> {
+        read_toml(&self.path)
+    }
+
+    fn save(&mut self, token: &StoredToken) -> Result<(), AuthError> {
+        write_toml(&self.path, token)
+    }
+
+    fn clear(&mut self) -> Result<(), AuthError> {
+        remove_file(&self.path)
+    }
+}
+
+/// TOML 파일에 refresh token 을 passphrase 로 암호화해서 저장
+#[derive(Clone)]
+pub struct EncryptedFileTokenStore {
+    path: PathBuf,
+    passphrase: String,
+}
+
+impl EncryptedFileTokenStore {
+    pub fn new<P: AsRef<Path>>(path: P, passphrase: &str) -> EncryptedFileTokenStore {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            passphrase: passphrase.to_string(),
+        }
+    }
+}
+
+impl TokenStore for EncryptedFileTokenStore {
+    /// passphrase 가 틀리면 Err
+    fn load(&mut self) -> Result<Option<StoredToken>, AuthError> {
+        let Some(data) = read_toml::<EncryptedStoredToken>(&self.path)? else {
+            return Ok(None);
+        };
+        let refresh_token = secret::decrypt(&self.passphrase, &data.encrypted_refresh_token)?;
+        Ok(Some(StoredToken {
+            user_id: data.user_id,
+            refresh_token,
+        }))
+    }
+
+    fn save(&mut self, token: &StoredToken) -> Result<(), AuthError> {
+        let data = EncryptedStoredToken {
+            user_id: token.user_id.clone(),
+            encrypted_refresh_token: secret::encrypt(&self.passphrase, &token.refresh_token)?,
+        };
+        write_toml(&self.path, &data)
+    }
+
+    fn clear(&mut self) -> Result<(), AuthError> {
+        remove_file(&self.path)
+    }
+}
+
+fn read_toml<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, AuthError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let toml_string = fs::read_to_string(path)
+        .map_err(|err| AuthError::GeneralErrorStr(format!("token file read fail. {}", err)))?;
+    toml::from_str(&toml_string)
+        .map(Some)
+        .map_err(|err| AuthError::GeneralErrorStr(format!("token file parse fail. {}", err)))
+}
+
+/// 임시 파일에 쓰고 교체 (0600)
+pub fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    if let Some(parent_dir) = path.parent() {
+        if !parent_dir.as_os_str().is_empty() && !parent_dir.exists() {
+            fs::create_dir_all(parent_dir)?;
+        }
+    }
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut open_options = OpenOptions::new();
+    open_options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.mode(0o600);
+    }
+    let result = open_options
+        .open(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+fn write_toml<T: Serialize>(path: &Path, data: &T) -> Result<(), AuthError> {
+    let toml_string = toml::to_string(data)
+        .map_err(|err| AuthError::GeneralErrorStr(format!("token serialize fail. {}", err)))?;
+    write_private(path, toml_string.as_bytes())
+        .map_err(|err| AuthError::GeneralErrorStr(format!("token file save fail. {}", err)))
+}
+
+fn remove_file(path: &Path) -> Result<(), AuthError> {
+    match fs::remove_file(path) {
+        Ok(_) => Ok(()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(AuthError::GeneralErrorStr(format!(
+            "token file remove fail. {}",
+            err
+        ))),
+    }
+}
@@ -0,0 +1,31 @@
+use crate::auth::error_types::AuthError;
+
+use super::{datatypes::StoredToken, TokenStore};
+
+/// 메모리에만 보관 (프로그램이 끝나면 사라진다)
+#[derive(Debug, Clone, Default)]
+pub struct MemoryTokenStore {
+    token: Option<StoredToken>,
+}
+
+impl MemoryTokenStore {
+    pub fn new(token: Option<StoredToken>) -> MemoryTokenStore {
+        Self { token }
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn load(&mut self) -> Result<Option<StoredToken>, AuthError> {
+        Ok(self.token.clone())
+    }
+
+    fn save(&mut self, token: &StoredToken) -> Result<(), AuthError> {
+        self.token = Some(token.clone());
+        Ok(())
+    }
+
+    fn clear(&mut self) -> Result<(), AuthError> {
+        self.token = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+pub mod datatypes;
+pub mod file;
+pub mod memory;
+pub mod secret;
+
+use crate::auth::error_types::AuthError;
+
+use self::datatypes::StoredToken;
+
+/// 로그인 정보 저장소
+/// - 새 토큰을 받을 때마다 save 가 호출된다. (로그인, refresh)
+/// - 구현: MemoryTokenStore, FileTokenStore, EncryptedFileTokenStore
+pub trait TokenStore: Send {
+    /// 저장된 로그인 정보. 없으면 None
+    fn load(&mut self) -> Result<Option<StoredToken>, AuthError>;
+    fn save(&mut self, token: &StoredToken) -> Result<(), AuthError>;
+    /// 로그아웃
+    fn clear(&mut self) -> Result<(), AuthError>;
+}
@@ -0,0 +1,58 @@
+use crate::auth::error_types::AuthError;
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// 암호화 형식 버전
+const SECRET_PREFIX: &str = "v1:";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// passphrase 와 salt 로 키 생성 (argon2id)
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key<Aes256Gcm>, AuthError> {
+    let mut key = Key::<Aes256Gcm>::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|err| {
+            AuthError::GeneralErrorStr(format!("passphrase key derive fail. {}", err))
+        })?;
+    Ok(key)
+}
+
+/// 암호화. 결과는 "v1:" + base64(salt + nonce + 암호문)
+pub fn encrypt(passphrase: &str, plain_text: &str) -> Result<String, AuthError> {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let key = derive_key(passphrase, &salt)?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let cipher_text = Aes256Gcm::new(&key)
+        .encrypt(&nonce, plain_text.as_bytes())
+        .map_err(|_| AuthError::GeneralError("encrypt fail."))?;
+
+    let mut data = Vec::with_capacity(SALT_LEN + NONCE_LEN + cipher_text.len());
+    data.extend_from_slice(&salt);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&cipher_text);
+    Ok(format!("{}{}", SECRET_PREFIX, STANDARD.encode(data)))
+}
+
+/// 복호화. passphrase 가 틀리면 오류
+pub fn decrypt(passphrase: &str, secret: &str) -> Result<String, AuthError> {
+    let data = secret
+        .strip_prefix(SECRET_PREFIX)
+        .and_then(|f| STANDARD.decode(f).ok())
+        .filter(|f| f.len() > SALT_LEN + NONCE_LEN)
+        .ok_or(AuthError::GeneralError("invalid encrypted value."))?;
+    let (salt, data) = data.split_at(SALT_LEN);
+    let (nonce, cipher_text) = data.split_at(NONCE_LEN);
+
+    let key = derive_key(passphrase, salt)?;
+    let plain_text = Aes256Gcm::new(&key)
+        .decrypt(Nonce::from_slice(nonce), cipher_text)
+        .map_err(|_| AuthError::GeneralError("wrong passphrase."))?;
+    String::from_utf8(plain_text).map_err(|_| AuthError::GeneralError("invalid encrypted value."))
+}
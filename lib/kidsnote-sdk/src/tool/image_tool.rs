@@ -1,12 +1,150 @@
-use std::{fs::{self, File}, io::Write};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
 use filetime::FileTime;
-use image::{Rgb, RgbImage};
+use image::{
+    codecs::{jpeg::JpegEncoder, webp::WebPEncoder},
+    ColorType, ImageOutputFormat, RgbImage,
+};
+use imageproc::rect::Rect;
 use little_exif::{exif_tag::ExifTag, metadata::Metadata};
-use rusttype::{Font, Scale};
+use rusttype::Scale;
 
-use crate::auth::error_types::AuthError;
+use crate::{auth::error_types::AuthError, child::KidsnoteChildSdk};
+
+use super::{
+    render_theme::{parse_color, RenderFormat, RenderTheme, ReportHeaderOptions, TextStyle},
+    text_layout::TextLayout,
+};
+
+/// 이미지로 그릴 알림장
+#[derive(Debug, Clone)]
+pub struct RenderReport {
+    pub title: String,
+    pub center_name: Option<String>,
+    pub author_name: String,
+    pub contents: Vec<String>,
+    pub report_id: Option<u64>,
+    pub report_date: DateTime<Utc>,
+    /// 알림장 날씨 (API 값)
+    pub weather: Option<String>,
+    pub class_name: Option<String>,
+    /// 자녀 생일 (yyyy-mm-dd)
+    pub child_date_birth: Option<String>,
+    /// 부모가 읽은 시간 (API 값)
+    pub read_at: Option<String>,
+}
+
+impl RenderReport {
+    /// 작성자가 원이 아니면 제목에 원 이름을 붙인다.
+    pub fn title_line(&self) -> String {
+        match &self.center_name {
+            Some(center_name)
+                if !center_name.is_empty()
+                    && center_name != &self.author_name
+                    && !self.author_name.contains(center_name.as_str()) =>
+            {
+                format!("{} ({})", self.title, center_name)
+            }
+            _ => self.title.clone(),
+        }
+    }
+
+    pub fn weather_line(&self) -> Option<String> {
+        self.weather
+            .as_deref()
+            .filter(|f| !f.is_empty())
+            .map(|f| format!("날씨 : {}", KidsnoteChildSdk::weather_label(f)))
+    }
+
+    /// 알림장 날짜의 만 나이 (년, 개월)
+    pub fn child_age(&self) -> Option<(i32, u32)> {
+        let birth =
+            NaiveDate::parse_from_str(self.child_date_birth.as_deref()?, "%Y-%m-%d").ok()?;
+        let date = self
+            .report_date
+            .with_timezone(&Self::local_offset())
+            .date_naive();
+        let mut months =
+            (date.year() - birth.year()) * 12 + date.month() as i32 - birth.month() as i32;
+        if date.day() < birth.day() {
+            months -= 1;
+        }
+        if months < 0 {
+            return None;
+        }
+        Some((months / 12, (months % 12) as u32))
+    }
+
+    pub fn age_line(&self, korean_age: bool) -> Option<String> {
+        let (years, months) = self.child_age()?;
+        let mut age = if years == 0 {
+            format!("나이 : {}개월", months)
+        } else {
+            format!("나이 : {}세 {}개월", years, months)
+        };
+        if korean_age {
+            let date = self.report_date.with_timezone(&Self::local_offset());
+            let birth_year = self
+                .child_date_birth
+                .as_deref()?
+                .get(..4)?
+                .parse::<i32>()
+                .ok()?;
+            age.push_str(&format!(" (한국 나이 {}세)", date.year() - birth_year + 1));
+        }
+        Some(age)
+    }
+
+    /// 읽은 시간. 날짜 형식이 아니면 그대로 쓴다.
+    pub fn read_line(&self) -> Option<String> {
+        let read_at = self.read_at.as_deref().filter(|f| !f.is_empty())?;
+        let read_at = match DateTime::parse_from_rfc3339(read_at) {
+            Ok(read_at) => read_at
+                .with_timezone(&Self::local_offset())
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            Err(_) => read_at.to_string(),
+        };
+        Some(format!("읽음 : {}", read_at))
+    }
+
+    /// 작성자 아래에 표시할 정보
+    pub fn header_lines(&self, header: &ReportHeaderOptions) -> Vec<String> {
+        let mut lines = Vec::new();
+        if header.class_name {
+            if let Some(class_name) = self.class_name.as_deref().filter(|f| !f.is_empty()) {
+                lines.push(format!("반 : {}", class_name));
+            }
+        }
+        if header.weather {
+            lines.extend(self.weather_line());
+        }
+        if header.age {
+            lines.extend(self.age_line(header.korean_age));
+        }
+        if header.read_at {
+            lines.extend(self.read_line());
+        }
+        lines
+    }
+
+    /// 알림장 날짜 기준 시간대 (Asia/Seoul)
+    fn local_offset() -> FixedOffset {
+        FixedOffset::east_opt(9 * 3600).unwrap()
+    }
+
+    pub fn footer_line(&self) -> String {
+        let date = KidsnoteChildSdk::report_local_date(self.report_date);
+        match self.report_id {
+            Some(report_id) => format!("#{} · {}", report_id, date),
+            None => date,
+        }
+    }
+}
 
 pub struct ImageTool {}
 
@@ -19,75 +157,406 @@ impl ImageTool {
         file_path: &str,
         file_date: DateTime<Utc>,
     ) -> Result<(), AuthError> {
-        let final_title = match center_name {
-            Some(center_name) 
-                if center_name != author_name 
-                && author_name.find(center_name).is_none() 
-            => {
-                format!("{} ({})\n", title, center_name)
-            }
-            _ => {
-                format!("{}\n", title)
-            }
+        let report = RenderReport {
+            title: title.to_string(),
+            center_name: center_name.clone(),
+            author_name: author_name.to_string(),
+            contents: contents.iter().map(|f| f.to_string()).collect(),
+            report_id: None,
+            report_date: file_date,
+            weather: None,
+            class_name: None,
+            child_date_birth: None,
+            read_at: None,
         };
-        let mut max_len = final_title.len();
-        for text in contents {
-            if let Some(offset) =  text.find('\n') {
-                if max_len < offset {
-                    max_len = offset;
-                }
-            } else {
-                if max_len < text.len() {
-                    max_len = text.len();
-                }
-            }
+        // 파일 확장자의 형식으로 저장
+        let mut theme = RenderTheme::default();
+        if let Some(format) = std::path::Path::new(file_path)
+            .extension()
+            .and_then(|f| f.to_str())
+            .and_then(RenderFormat::from_extension)
+        {
+            theme.format = format;
         }
+        Self::render_report_file(&report, &theme, file_path)
+    }
 
-        let width = (max_len as u32) * 8 + 20;
-        let height: u32 = ((contents.len() + 1) as u32) * 42 + 50;
+    /// 테마로 알림장 이미지 그리기
+    /// - 글자 폭으로 줄을 나누고, 그려진 글자에 맞춰 이미지 크기를 정한다.
+    pub fn render_report(
+        report: &RenderReport,
+        theme: &RenderTheme,
+    ) -> Result<RgbImage, AuthError> {
+        theme.validate()?;
+        let padding = theme.padding as f32;
+        let max_text_width = (theme.page_width - theme.padding * 2) as f32;
 
-        let mut img = RgbImage::new(width, height);
+        let fallback_fonts = theme.load_fallback_fonts()?;
+        let title_font = theme.title.font();
+        let author_font = theme.author.font();
+        let body_font = theme.body.font();
+        let footer_font = theme.footer.as_ref().map(|f| f.font());
+        let title_layout = TextLayout::new(&title_font, Scale::uniform(theme.title.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+        let author_layout = TextLayout::new(&author_font, Scale::uniform(theme.author.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+        let body_layout = TextLayout::new(&body_font, Scale::uniform(theme.body.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+        let footer_layout = theme
+            .footer
+            .as_ref()
+            .zip(footer_font.as_ref())
+            .map(|(style, font)| {
+                TextLayout::new(font, Scale::uniform(style.size))
+                    .line_spacing(theme.line_spacing)
+                    .fallback_fonts(&fallback_fonts)
+            });
 
-        // 배경을 흰색으로 채우기
-        for pixel in img.pixels_mut() {
-            *pixel = Rgb([255, 255, 255]);
+        let title_lines = title_layout.wrap(&report.title_line(), max_text_width);
+        let mut author_lines = author_layout.wrap(
+            &format!("{}{}", theme.author_label, report.author_name),
+            max_text_width,
+        );
+        for line in report.header_lines(&theme.header) {
+            author_lines.extend(author_layout.wrap(&line, max_text_width));
         }
+        let mut body_lines = Vec::new();
+        for text in &report.contents {
+            body_lines.extend(body_layout.wrap(text, max_text_width));
+        }
+        let footer_lines = footer_layout
+            .as_ref()
+            .map(|layout| layout.wrap(&report.footer_line(), max_text_width))
+            .unwrap_or_default();
+
+        let width = if theme.fixed_width {
+            theme.page_width
+        } else {
+            let text_width = title_layout
+                .max_width(&title_lines)
+                .max(author_layout.max_width(&author_lines))
+                .max(body_layout.max_width(&body_lines))
+                .max(
+                    footer_layout
+                        .as_ref()
+                        .map(|f| f.max_width(&footer_lines))
+                        .unwrap_or(0.0),
+                );
+            (text_width.ceil() as u32 + theme.padding * 2).min(theme.page_width)
+        };
 
-        // 텍스트 추가
-        let font_bytes = include_bytes!("../../fonts/NanumSquareL.ttf"); // 여기에 사용할 폰트 파일 경로를 지정하세요
-        let font = Font::try_from_bytes(font_bytes as &[u8]).expect("Unable to load font");
-        let scale = Scale { x: 24.0, y: 24.0 }; // 폰트 크기 조정
+        let header_height = padding
+            + title_lines.len() as f32 * title_layout.line_height()
+            + author_lines.len() as f32 * author_layout.line_height()
+            + if theme.header_band.is_some() {
+                padding / 2.0
+            } else {
+                0.0
+            };
+        let body_top = header_height
+            + if theme.header_band.is_some() {
+                padding
+            } else {
+                padding / 2.0
+            };
+        let footer_top = body_top + body_lines.len() as f32 * body_layout.line_height();
+        let footer_height = footer_layout
+            .as_ref()
+            .map(|f| padding / 2.0 + footer_lines.len() as f32 * f.line_height())
+            .unwrap_or(0.0);
+        let height = (footer_top + footer_height + padding).ceil() as u32;
 
-        // 제목
-        imageproc::drawing::draw_text_mut(&mut img, Rgb([0, 0, 0]), 10, 10, scale, &font, &final_title);
+        let mut img = RgbImage::from_pixel(width, height, parse_color(&theme.background)?);
+        if let Some(header_band) = &theme.header_band {
+            let rect = Rect::at(0, 0).of_size(width, header_height.ceil() as u32);
+            imageproc::drawing::draw_filled_rect_mut(&mut img, rect, parse_color(header_band)?);
+        }
 
-        // 작성자
-        imageproc::drawing::draw_text_mut(
+        // 제목, 작성자
+        let x = theme.padding as i32;
+        let y_position = Self::draw_lines(
             &mut img,
-            Rgb([0, 0, 0]),
-            10,
-            40,
-            scale,
-            &font,
-            &format!("작성자 : {}", author_name),
-        );
+            &title_layout,
+            &theme.title,
+            x,
+            padding,
+            &title_lines,
+        )?;
+        Self::draw_lines(
+            &mut img,
+            &author_layout,
+            &theme.author,
+            x,
+            y_position,
+            &author_lines,
+        )?;
 
         // 내용
-        let mut y_position = 90.0;
-        for text in contents {
-            let text = Self::wrap_text(text, 40);
-            imageproc::drawing::draw_text_mut(
+        Self::draw_lines(
+            &mut img,
+            &body_layout,
+            &theme.body,
+            x,
+            body_top,
+            &body_lines,
+        )?;
+
+        // 꼬리말
+        if let (Some(style), Some(layout)) = (&theme.footer, &footer_layout) {
+            Self::draw_lines(
+                &mut img,
+                layout,
+                style,
+                x,
+                footer_top + padding / 2.0,
+                &footer_lines,
+            )?;
+        }
+        Ok(img)
+    }
+
+    /// 여러 줄 그리기. 다음 줄의 위치를 반환한다.
+    fn draw_lines(
+        img: &mut RgbImage,
+        layout: &TextLayout,
+        style: &TextStyle,
+        x: i32,
+        y: f32,
+        lines: &[String],
+    ) -> Result<f32, AuthError> {
+        let color = parse_color(&style.color)?;
+        let mut y_position = y;
+        for line in lines {
+            layout.draw_line(img, color, x, y_position as i32, line);
+            y_position += layout.line_height();
+        }
+        Ok(y_position)
+    }
+
+    /// 알림장 내용과 사진을 함께 그린 카드
+    /// - 사진은 정사각형으로 잘라 columns 칸씩 놓는다. 읽을 수 없는 사진은 건너뛴다.
+    /// - max_height 를 넘으면 여러 장으로 나누고, 장마다 제목과 쪽 번호를 그린다.
+    pub fn render_report_card(
+        report: &RenderReport,
+        photo_files: &[String],
+        theme: &RenderTheme,
+    ) -> Result<Vec<RgbImage>, AuthError> {
+        theme.validate()?;
+        let card = &theme.report_card;
+        let width = theme.page_width;
+        let padding = theme.padding as f32;
+        let inner_width = theme.page_width - theme.padding * 2;
+        let max_text_width = inner_width as f32;
+
+        // 꼬리말이 없는 테마는 쪽 번호만 본문 글자로 작게 그린다.
+        let footer_style = theme.footer.clone().unwrap_or(TextStyle {
+            size: (theme.body.size * 0.7).max(4.0),
+            ..theme.body.clone()
+        });
+        let fallback_fonts = theme.load_fallback_fonts()?;
+        let title_font = theme.title.font();
+        let author_font = theme.author.font();
+        let body_font = theme.body.font();
+        let footer_font = footer_style.font();
+        let title_layout = TextLayout::new(&title_font, Scale::uniform(theme.title.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+        let author_layout = TextLayout::new(&author_font, Scale::uniform(theme.author.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+        let body_layout = TextLayout::new(&body_font, Scale::uniform(theme.body.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+        let footer_layout = TextLayout::new(&footer_font, Scale::uniform(footer_style.size))
+            .line_spacing(theme.line_spacing)
+            .fallback_fonts(&fallback_fonts);
+
+        let title_lines = title_layout.wrap(&report.title_line(), max_text_width);
+        // 카드에는 날씨를 항상 그린다.
+        let header = ReportHeaderOptions {
+            weather: true,
+            ..theme.header.clone()
+        };
+        let mut author_lines = author_layout.wrap(
+            &format!("{}{}", theme.author_label, report.author_name),
+            max_text_width,
+        );
+        for line in report.header_lines(&header) {
+            author_lines.extend(author_layout.wrap(&line, max_text_width));
+        }
+        let mut body_lines = Vec::new();
+        for text in &report.contents {
+            body_lines.extend(body_layout.wrap(text, max_text_width));
+        }
+
+        // 사진 칸
+        let cell = (inner_width - card.spacing * (card.columns - 1)) / card.columns;
+        let mut thumbnails = Vec::new();
+        for photo_file in photo_files {
+            match image::open(photo_file) {
+                Ok(photo) => thumbnails.push(Self::square_thumbnail(&photo.to_rgb8(), cell)),
+                Err(err) => {
+                    log::warn!(target:"image", "report card photo skip. path={}, {}", photo_file, err);
+                }
+            }
+        }
+
+        let band_gap = if theme.header_band.is_some() {
+            padding / 2.0
+        } else {
+            0.0
+        };
+        let header_height = padding
+            + title_lines.len() as f32 * title_layout.line_height()
+            + author_lines.len() as f32 * author_layout.line_height()
+            + band_gap;
+        let body_top = header_height + padding / 2.0 + band_gap;
+        let footer_height = padding / 2.0 + footer_layout.line_height();
+
+        // 한 장에 들어갈 만큼씩 나누기
+        enum Block<'a> {
+            Line(&'a String),
+            Photos(&'a [RgbImage]),
+        }
+        let row_height = (cell + card.spacing) as f32;
+        let mut blocks: Vec<(Block, f32)> = body_lines
+            .iter()
+            .map(|f| (Block::Line(f), body_layout.line_height()))
+            .collect();
+        for (index, row) in thumbnails.chunks(card.columns as usize).enumerate() {
+            // 글과 첫 줄 사진 사이 간격
+            let gap = if index == 0 && !body_lines.is_empty() {
+                padding / 2.0
+            } else {
+                0.0
+            };
+            blocks.push((Block::Photos(row), row_height + gap));
+        }
+        let max_content_height = card
+            .max_height
+            .map(|f| f as f32 - body_top - footer_height - padding)
+            .unwrap_or(f32::MAX)
+            .max(row_height);
+        let mut pages: Vec<Vec<(Block, f32)>> = vec![Vec::new()];
+        let mut page_height = 0.0;
+        for (block, height) in blocks {
+            let page = pages.last_mut().unwrap();
+            if !page.is_empty() && page_height + height > max_content_height {
+                pages.push(Vec::new());
+                page_height = 0.0;
+            }
+            page_height += height;
+            pages.last_mut().unwrap().push((block, height));
+        }
+
+        let page_count = pages.len();
+        let background = parse_color(&theme.background)?;
+        let x = theme.padding as i32;
+        let mut images = Vec::new();
+        for (page_index, page) in pages.into_iter().enumerate() {
+            let content_height: f32 = page.iter().map(|(_, height)| height).sum();
+            let footer_top = body_top + content_height;
+            let height = (footer_top + footer_height + padding).ceil() as u32;
+            let mut img = RgbImage::from_pixel(width, height, background);
+            if let Some(header_band) = &theme.header_band {
+                let rect = Rect::at(0, 0).of_size(width, header_height.ceil() as u32);
+                imageproc::drawing::draw_filled_rect_mut(&mut img, rect, parse_color(header_band)?);
+            }
+
+            // 제목, 작성자, 날씨
+            let y_position = Self::draw_lines(
+                &mut img,
+                &title_layout,
+                &theme.title,
+                x,
+                padding,
+                &title_lines,
+            )?;
+            Self::draw_lines(
                 &mut img,
-                Rgb([0, 0, 0]),
-                10,
-                y_position as i32,
-                scale,
-                &font,
-                text.as_str(),
-            );
-            y_position += scale.y * 1.5; // 행 간격 설정
+                &author_layout,
+                &theme.author,
+                x,
+                y_position,
+                &author_lines,
+            )?;
+
+            // 내용, 사진
+            let body_color = parse_color(&theme.body.color)?;
+            let mut y_position = body_top;
+            for (block, height) in page {
+                match block {
+                    Block::Line(line) => {
+                        body_layout.draw_line(&mut img, body_color, x, y_position as i32, line);
+                    }
+                    Block::Photos(row) => {
+                        let photo_top = (y_position + height - row_height) as i64;
+                        for (column, thumbnail) in row.iter().enumerate() {
+                            let photo_left =
+                                x as i64 + (column as u32 * (cell + card.spacing)) as i64;
+                            image::imageops::overlay(&mut img, thumbnail, photo_left, photo_top);
+                        }
+                    }
+                }
+                y_position += height;
+            }
+
+            // 꼬리말, 쪽 번호
+            let mut footer_text = if theme.footer.is_some() {
+                report.footer_line()
+            } else {
+                String::new()
+            };
+            if page_count > 1 {
+                if !footer_text.is_empty() {
+                    footer_text.push_str(" · ");
+                }
+                footer_text.push_str(&format!("{}/{}", page_index + 1, page_count));
+            }
+            if !footer_text.is_empty() {
+                Self::draw_lines(
+                    &mut img,
+                    &footer_layout,
+                    &footer_style,
+                    x,
+                    footer_top + padding / 2.0,
+                    &[footer_text],
+                )?;
+            }
+            images.push(img);
         }
+        Ok(images)
+    }
+
+    /// 가운데를 정사각형으로 잘라 size 로 줄인다.
+    pub fn square_thumbnail(photo: &RgbImage, size: u32) -> RgbImage {
+        let side = photo.width().min(photo.height());
+        let x = (photo.width() - side) / 2;
+        let y = (photo.height() - side) / 2;
+        let square = image::imageops::crop_imm(photo, x, y, side, side).to_image();
+        image::imageops::resize(&square, size, size, image::imageops::FilterType::Triangle)
+    }
 
+    /// 알림장 이미지를 테마 형식으로 저장하고, 촬영 날짜와 파일 날짜를 알림장 날짜로 맞춘다.
+    pub fn render_report_file(
+        report: &RenderReport,
+        theme: &RenderTheme,
+        file_path: &str,
+    ) -> Result<(), AuthError> {
+        let img = Self::render_report(report, theme)?;
+        Self::save_report_image(&img, theme, file_path, report.report_date)
+    }
+
+    /// 알림장 이미지 저장 후 촬영 날짜와 파일 날짜를 알림장 날짜로 맞춘다.
+    pub fn save_report_image(
+        img: &RgbImage,
+        theme: &RenderTheme,
+        file_path: &str,
+        file_date: DateTime<Utc>,
+    ) -> Result<(), AuthError> {
         if let Some(parent_dir) = std::path::Path::new(file_path).parent() {
             if !parent_dir.exists() {
                 match fs::create_dir_all(parent_dir) {
@@ -97,17 +566,16 @@ impl ImageTool {
             }
         }
 
-        img.save(file_path).expect("Unable to save image");
+        Self::save_image(img, theme, file_path)?;
 
         // exif : https://www.awaresystems.be/imaging/tiff/tifftags/privateifd/exif.html
         let mut metadata = Metadata::new();
-        metadata.set_tag(
-            ExifTag::DateTimeOriginal(file_date.format("%Y-%m-%d %H:%M:%S").to_string()),
-        );
-        metadata.set_tag(
-            ExifTag::DateTimeOriginal(file_date.format("%Y-%m-%d %H:%M:%S").to_string())
-        );
-        metadata.write_to_file(std::path::Path::new(file_path)).expect("image esif write fail.");
+        metadata.set_tag(ExifTag::DateTimeOriginal(
+            file_date.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ));
+        if let Err(err) = metadata.write_to_file(std::path::Path::new(file_path)) {
+            log::warn!(target:"image", "image exif write fail. path={}, {}", file_path, err);
+        }
 
         // 파일 날짜
         let file_time = FileTime::from_unix_time(file_date.timestamp(), 0);
@@ -118,6 +586,74 @@ impl ImageTool {
         Ok(())
     }
 
+    /// 테마의 형식으로 저장
+    pub fn save_image(
+        img: &RgbImage,
+        theme: &RenderTheme,
+        file_path: &str,
+    ) -> Result<(), AuthError> {
+        let save_error = |err: String| {
+            AuthError::GeneralErrorStr(format!("image save fail. path={}, {}", file_path, err))
+        };
+        let file = File::create(file_path).map_err(|err| save_error(err.to_string()))?;
+        let mut writer = BufWriter::new(file);
+        let result = match theme.format {
+            RenderFormat::Jpeg => {
+                JpegEncoder::new_with_quality(&mut writer, theme.jpeg_quality).encode_image(img)
+            }
+            RenderFormat::Png => img.write_to(&mut writer, ImageOutputFormat::Png),
+            RenderFormat::Webp => WebPEncoder::new_lossless(&mut writer).encode(
+                img.as_raw(),
+                img.width(),
+                img.height(),
+                ColorType::Rgb8,
+            ),
+        };
+        result.map_err(|err| save_error(err.to_string()))?;
+        writer.flush().map_err(|err| save_error(err.to_string()))
+    }
+
+    /// 알림장 텍스트 파일 저장
+    /// - 제목, 작성자, 작성자 아래 정보, 구분선(---), 내용 순서
+    /// - 내용은 테마의 txt_wrap 글자 수로 나눈다.
+    pub fn render_report_txt_file(
+        report: &RenderReport,
+        theme: &RenderTheme,
+        file_path: &str,
+    ) -> Result<(), AuthError> {
+        let mut text = format!("{}\n작성자 : {}\n", report.title_line(), report.author_name);
+        for line in report.header_lines(&theme.header) {
+            text.push_str(&format!("{}\n", line));
+        }
+        text.push_str("---\n");
+        for content in &report.contents {
+            if theme.text.txt_wrap > 0 {
+                text.push_str(&Self::wrap_text(content, theme.text.txt_wrap));
+            } else {
+                text.push_str(content);
+            }
+            text.push('\n');
+        }
+        if let Some(parent_dir) = std::path::Path::new(file_path).parent() {
+            fs::create_dir_all(parent_dir).map_err(|err| {
+                AuthError::GeneralErrorStr(format!(
+                    "text dir create fail. path={}, {}",
+                    file_path, err
+                ))
+            })?;
+        }
+        fs::write(file_path, text).map_err(|err| {
+            AuthError::GeneralErrorStr(format!("text save fail. path={}, {}", file_path, err))
+        })?;
+
+        // 파일 날짜
+        let file_time = FileTime::from_unix_time(report.report_date.timestamp(), 0);
+        if let Err(err) = filetime::set_file_times(file_path, file_time, file_time) {
+            log::warn!(target:"image", "set_file_times fail. path={}, {}", file_path, err);
+        }
+        Ok(())
+    }
+
     pub fn text_to_txt_file(
         title: &str,
         center_name: &Option<String>,
@@ -1 +1,3 @@
 pub mod image_tool;
+pub mod render_theme;
+pub mod text_layout;
@@ -0,0 +1,433 @@
+use std::fs;
+
+use image::Rgb;
+use rusttype::Font;
+use serde::{Deserialize, Serialize};
+
+use crate::auth::error_types::AuthError;
+
+/// 기호, 이모티콘을 그리는 내장 글꼴 (DejaVu Sans, 흑백)
+const SYMBOL_FONT: &[u8] = include_bytes!("../../fonts/DejaVuSans.ttf");
+/// 기호 글꼴에 없는 이모지를 그리는 내장 글꼴 (Noto Emoji, 흑백)
+const EMOJI_FONT: &[u8] = include_bytes!("../../fonts/NotoEmoji-Regular.ttf");
+
+/// 내장 글꼴
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FontFamily {
+    NanumSquare,
+    /// 나눔스퀘어 라운드
+    NanumSquareAc,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FontWeight {
+    Light,
+    Regular,
+    Bold,
+    ExtraBold,
+}
+
+impl FontFamily {
+    pub fn font_bytes(&self, weight: FontWeight) -> &'static [u8] {
+        match (self, weight) {
+            (FontFamily::NanumSquare, FontWeight::Light) => {
+                include_bytes!("../../fonts/NanumSquareL.ttf")
+            }
+            (FontFamily::NanumSquare, FontWeight::Regular) => {
+                include_bytes!("../../fonts/NanumSquareR.ttf")
+            }
+            (FontFamily::NanumSquare, FontWeight::Bold) => {
+                include_bytes!("../../fonts/NanumSquareB.ttf")
+            }
+            (FontFamily::NanumSquare, FontWeight::ExtraBold) => {
+                include_bytes!("../../fonts/NanumSquareEB.ttf")
+            }
+            (FontFamily::NanumSquareAc, FontWeight::Light) => {
+                include_bytes!("../../fonts/NanumSquare_acL.ttf")
+            }
+            (FontFamily::NanumSquareAc, FontWeight::Regular) => {
+                include_bytes!("../../fonts/NanumSquare_acR.ttf")
+            }
+            (FontFamily::NanumSquareAc, FontWeight::Bold) => {
+                include_bytes!("../../fonts/NanumSquare_acB.ttf")
+            }
+            (FontFamily::NanumSquareAc, FontWeight::ExtraBold) => {
+                include_bytes!("../../fonts/NanumSquare_acEB.ttf")
+            }
+        }
+    }
+}
+
+/// 글자 모양
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TextStyle {
+    pub family: FontFamily,
+    pub weight: FontWeight,
+    /// 글자 크기 (px)
+    pub size: f32,
+    /// #rrggbb
+    pub color: String,
+}
+
+impl TextStyle {
+    pub fn new(family: FontFamily, weight: FontWeight, size: f32, color: &str) -> Self {
+        Self {
+            family,
+            weight,
+            size,
+            color: color.to_string(),
+        }
+    }
+
+    pub fn font(&self) -> Font<'static> {
+        Font::try_from_bytes(self.family.font_bytes(self.weight)).expect("Unable to load font")
+    }
+}
+
+/// 저장 형식
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderFormat {
+    Jpeg,
+    Png,
+    /// 무손실 WebP
+    Webp,
+}
+
+impl RenderFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RenderFormat::Jpeg => "jpg",
+            RenderFormat::Png => "png",
+            RenderFormat::Webp => "webp",
+        }
+    }
+
+    pub fn from_extension(extension: &str) -> Option<RenderFormat> {
+        match extension.to_lowercase().as_str() {
+            "jpg" | "jpeg" => Some(RenderFormat::Jpeg),
+            "png" => Some(RenderFormat::Png),
+            "webp" => Some(RenderFormat::Webp),
+            _ => None,
+        }
+    }
+}
+
+/// 사진을 함께 그리는 알림장 카드
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ReportCardLayout {
+    /// 사진 한 줄의 칸 수
+    pub columns: u32,
+    /// 사진 사이 간격 (px)
+    pub spacing: u32,
+    /// 한 장의 최대 높이 (px). 넘으면 여러 장으로 나눈다. 없으면 한 장에 모두 그린다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_height: Option<u32>,
+}
+
+impl Default for ReportCardLayout {
+    fn default() -> Self {
+        Self {
+            columns: 3,
+            spacing: 8,
+            max_height: Some(2400),
+        }
+    }
+}
+
+/// 작성자 아래에 표시할 정보 ([render.header]). 텍스트 파일에도 적용된다.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct ReportHeaderOptions {
+    /// 반 이름
+    pub class_name: bool,
+    /// 날씨
+    pub weather: bool,
+    /// 알림장 날짜의 자녀 나이 (만 나이 년, 개월)
+    pub age: bool,
+    /// 나이 뒤에 한국 나이를 붙인다.
+    pub korean_age: bool,
+    /// 부모가 읽은 시간
+    pub read_at: bool,
+}
+
+/// 알림장 내용 정리 ([render.text]). 원문은 .json 에 그대로 저장한다.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct TextOptions {
+    /// 이어진 띄어쓰기를 하나로 줄인다.
+    pub collapse_spaces: bool,
+    /// 줄 앞뒤 공백을 지운다.
+    pub trim_lines: bool,
+    /// .txt 한 줄의 글자 수. 0 이면 나누지 않는다.
+    pub txt_wrap: usize,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            collapse_spaces: true,
+            trim_lines: true,
+            txt_wrap: 40,
+        }
+    }
+}
+
+impl TextOptions {
+    /// 이미지, .txt 에 쓸 내용 줄
+    pub fn normalize(&self, content: &str) -> Vec<String> {
+        content
+            .lines()
+            .map(|line| {
+                let mut line = line.to_string();
+                if self.collapse_spaces {
+                    let mut collapsed = String::with_capacity(line.len());
+                    for c in line.chars() {
+                        if !(c == ' ' && collapsed.ends_with(' ')) {
+                            collapsed.push(c);
+                        }
+                    }
+                    line = collapsed;
+                }
+                if self.trim_lines {
+                    line = line.trim().to_string();
+                }
+                line
+            })
+            .collect()
+    }
+}
+
+/// 알림장 이미지 모양
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RenderTheme {
+    /// 이미지 최대 폭 (px)
+    pub page_width: u32,
+    /// true 면 글이 짧아도 page_width 로 그린다.
+    pub fixed_width: bool,
+    /// 여백 (px)
+    pub padding: u32,
+    /// 배경색 #rrggbb
+    pub background: String,
+    /// 줄 간격 (글자 크기의 배수)
+    pub line_spacing: f32,
+    pub title: TextStyle,
+    pub author: TextStyle,
+    pub body: TextStyle,
+    /// 작성자 앞에 붙는 글
+    pub author_label: String,
+    /// 제목, 작성자 뒤의 띠 색. 없으면 그리지 않는다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header_band: Option<String>,
+    /// 알림장 번호, 날짜. 없으면 그리지 않는다.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub footer: Option<TextStyle>,
+    pub format: RenderFormat,
+    /// JPEG 품질 (1~100)
+    pub jpeg_quality: u8,
+    /// 작성자 아래 정보 ([render.header])
+    #[serde(default)]
+    pub header: ReportHeaderOptions,
+    /// 내용 정리 ([render.text])
+    #[serde(default)]
+    pub text: TextOptions,
+    /// 사진 카드 ([render.report_card])
+    #[serde(default)]
+    pub report_card: ReportCardLayout,
+    /// 글꼴에 없는 글자(한자, 일본어, 이모지 등)를 그릴 글꼴 파일 (.ttf, .otf)
+    /// - 앞에서부터 글자가 있는 글꼴을 찾고, 마지막에 내장 기호 글꼴을 사용한다.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_fonts: Vec<String>,
+}
+
+impl Default for RenderTheme {
+    fn default() -> Self {
+        let style = TextStyle::new(FontFamily::NanumSquare, FontWeight::Light, 24.0, "#000000");
+        Self {
+            page_width: 760,
+            fixed_width: false,
+            padding: 20,
+            background: String::from("#ffffff"),
+            line_spacing: 1.5,
+            title: style.clone(),
+            author: style.clone(),
+            body: style,
+            author_label: String::from("작성자 : "),
+            header_band: None,
+            footer: None,
+            format: RenderFormat::Jpeg,
+            jpeg_quality: 90,
+            header: ReportHeaderOptions::default(),
+            text: TextOptions::default(),
+            report_card: ReportCardLayout::default(),
+            fallback_fonts: Vec::new(),
+        }
+    }
+}
+
+impl RenderTheme {
+    /// 내장 테마 이름
+    pub const PRESETS: [&'static str; 3] = ["default", "card", "dark"];
+
+    /// 내장 테마
+    /// - default : 흰 바탕에 검은 글씨
+    /// - card : 제목 띠와 꼬리말이 있는 카드
+    /// - dark : 어두운 바탕 (PNG)
+    pub fn preset(name: &str) -> Option<RenderTheme> {
+        match name {
+            "default" => Some(Self::default()),
+            "card" => Some(Self {
+                page_width: 800,
+                fixed_width: true,
+                padding: 32,
+                background: String::from("#fffdf7"),
+                line_spacing: 1.6,
+                title: TextStyle::new(
+                    FontFamily::NanumSquare,
+                    FontWeight::ExtraBold,
+                    30.0,
+                    "#ffffff",
+                ),
+                author: TextStyle::new(FontFamily::NanumSquare, FontWeight::Bold, 20.0, "#fff4e0"),
+                body: TextStyle::new(
+                    FontFamily::NanumSquare,
+                    FontWeight::Regular,
+                    24.0,
+                    "#333333",
+                ),
+                author_label: String::from("작성자 · "),
+                header_band: Some(String::from("#f29f3d")),
+                footer: Some(TextStyle::new(
+                    FontFamily::NanumSquare,
+                    FontWeight::Light,
+                    16.0,
+                    "#9a9a9a",
+                )),
+                format: RenderFormat::Jpeg,
+                jpeg_quality: 92,
+                header: ReportHeaderOptions::default(),
+                text: TextOptions::default(),
+                report_card: ReportCardLayout::default(),
+                fallback_fonts: Vec::new(),
+            }),
+            "dark" => Some(Self {
+                background: String::from("#1f2125"),
+                title: TextStyle::new(FontFamily::NanumSquareAc, FontWeight::Bold, 26.0, "#f5f5f5"),
+                author: TextStyle::new(
+                    FontFamily::NanumSquareAc,
+                    FontWeight::Regular,
+                    20.0,
+                    "#b0b4ba",
+                ),
+                body: TextStyle::new(
+                    FontFamily::NanumSquareAc,
+                    FontWeight::Regular,
+                    24.0,
+                    "#e8e8e8",
+                ),
+                footer: Some(TextStyle::new(
+                    FontFamily::NanumSquareAc,
+                    FontWeight::Light,
+                    16.0,
+                    "#7d8288",
+                )),
+                format: RenderFormat::Png,
+                ..Self::default()
+            }),
+            _ => None,
+        }
+    }
+
+    /// 대체 글꼴. 설정한 글꼴 파일 다음에 내장 기호 글꼴, 내장 이모지 글꼴
+    pub fn load_fallback_fonts(&self) -> Result<Vec<Font<'static>>, AuthError> {
+        let mut fonts = Vec::new();
+        for font_file in &self.fallback_fonts {
+            let font_bytes = fs::read(font_file).map_err(|err| {
+                AuthError::GeneralErrorStr(format!(
+                    "fallback font read fail. path={}, {}",
+                    font_file, err
+                ))
+            })?;
+            let font = Font::try_from_vec(font_bytes).ok_or_else(|| {
+                AuthError::GeneralErrorStr(format!("invalid fallback font. path={}", font_file))
+            })?;
+            fonts.push(font);
+        }
+        fonts.push(Font::try_from_bytes(SYMBOL_FONT).expect("Unable to load font"));
+        fonts.push(Font::try_from_bytes(EMOJI_FONT).expect("Unable to load font"));
+        Ok(fonts)
+    }
+
+    /// 색, 크기 확인
+    pub fn validate(&self) -> Result<(), AuthError> {
+        parse_color(&self.background)?;
+        if let Some(header_band) = &self.header_band {
+            parse_color(header_band)?;
+        }
+        for style in [&self.title, &self.author, &self.body]
+            .into_iter()
+            .chain(self.footer.as_ref())
+        {
+            parse_color(&style.color)?;
+            if !(4.0..=200.0).contains(&style.size) {
+                return Err(AuthError::GeneralErrorStr(format!(
+                    "invalid font size. size={}",
+                    style.size
+                )));
+            }
+        }
+        if self.page_width <= self.padding * 2 + 100 {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "page_width is too small. page_width={}, padding={}",
+                self.page_width, self.padding
+            )));
+        }
+        if !(0.5..=5.0).contains(&self.line_spacing) {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "invalid line_spacing. line_spacing={}",
+                self.line_spacing
+            )));
+        }
+        if !(1..=100).contains(&self.jpeg_quality) {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "invalid jpeg_quality. jpeg_quality={}",
+                self.jpeg_quality
+            )));
+        }
+        let card = &self.report_card;
+        if !(1..=8).contains(&card.columns) {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "invalid report_card columns. columns={}",
+                card.columns
+            )));
+        }
+        if (self.page_width - self.padding * 2)
+            < (card.columns - 1) * card.spacing + card.columns * 40
+        {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "report_card spacing is too large. spacing={}",
+                card.spacing
+            )));
+        }
+        if card.max_height.is_some_and(|f| f < self.page_width / 2) {
+            return Err(AuthError::GeneralErrorStr(format!(
+                "report_card max_height is too small. max_height={:?}",
+                card.max_height
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// #rrggbb
+pub fn parse_color(color: &str) -> Result<Rgb<u8>, AuthError> {
+    let invalid = || AuthError::GeneralErrorStr(format!("invalid color. color={}", color));
+    let hex = color.strip_prefix('#').ok_or_else(invalid)?;
+    if hex.len() != 6 {
+        return Err(invalid());
+    }
+    let value = u32::from_str_radix(hex, 16).map_err(|_| invalid())?;
+    Ok(Rgb([(value >> 16) as u8, (value >> 8) as u8, value as u8]))
+}
@@ -0,0 +1,202 @@
+use image::{Rgb, RgbImage};
+use rusttype::{point, Font, Scale};
+
+/// 어느 글꼴에도 없는 글자 대신 그리는 글자
+const REPLACEMENT_CHAR: char = '\u{FFFD}';
+
+/// 글꼴의 글자 폭으로 줄을 나누고 그린다.
+/// - 글꼴에 없는 글자는 fallback_fonts 에서 글자가 있는 첫 글꼴로 그린다.
+pub struct TextLayout<'a> {
+    font: &'a Font<'a>,
+    fallback_fonts: &'a [Font<'a>],
+    scale: Scale,
+    /// 줄 간격 (글자 크기의 배수)
+    line_spacing: f32,
+}
+
+impl<'a> TextLayout<'a> {
+    pub fn new(font: &'a Font<'a>, scale: Scale) -> Self {
+        Self {
+            font,
+            fallback_fonts: &[],
+            scale,
+            line_spacing: 1.5,
+        }
+    }
+
+    pub fn fallback_fonts(mut self, fallback_fonts: &'a [Font<'a>]) -> Self {
+        self.fallback_fonts = fallback_fonts;
+        self
+    }
+
+    pub fn line_spacing(mut self, line_spacing: f32) -> Self {
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    /// 한 줄의 높이 (px)
+    pub fn line_height(&self) -> f32 {
+        self.scale.y * self.line_spacing
+    }
+
+    /// 그렸을 때의 폭 (px). 줄바꿈은 고려하지 않는다.
+    pub fn text_width(&self, text: &str) -> f32 {
+        self.runs(text)
+            .iter()
+            .map(|(font, run)| self.run_width(font, run))
+            .sum()
+    }
+
+    fn run_width(&self, font: &Font, run: &str) -> f32 {
+        font.layout(run, self.scale, point(0.0, 0.0))
+            .last()
+            .map(|f| f.position().x + f.unpositioned().h_metrics().advance_width)
+            .unwrap_or(0.0)
+    }
+
+    /// 글자가 있는 첫 글꼴
+    fn font_for(&self, c: char) -> Option<&'a Font<'a>> {
+        std::iter::once(self.font)
+            .chain(self.fallback_fonts.iter())
+            .find(|f| Self::has_glyph(f, c))
+    }
+
+    /// 글꼴에 글자가 있는지 확인
+    /// - 한자처럼 글리프는 있지만 모양이 비어있는 글꼴이 있어서 공백이 아닌 글자는 모양까지 확인한다.
+    pub fn has_glyph(font: &Font, c: char) -> bool {
+        let glyph = font.glyph(c);
+        glyph.id().0 != 0
+            && (c.is_whitespace()
+                || glyph
+                    .scaled(Scale::uniform(16.0))
+                    .exact_bounding_box()
+                    .is_some())
+    }
+
+    /// 같은 글꼴로 그릴 글자끼리 묶는다.
+    /// - 이모지 변형 선택자처럼 폭이 없는 글자는 빼고, 어느 글꼴에도 없는 글자는 U+FFFD 로 바꾼다.
+    pub fn runs(&self, text: &str) -> Vec<(&'a Font<'a>, String)> {
+        let mut runs: Vec<(&'a Font<'a>, String)> = Vec::new();
+        for c in text.chars().filter(|f| !Self::is_ignorable(*f)) {
+            let Some((font, c)) = self.font_for(c).map(|font| (font, c)).or_else(|| {
+                self.font_for(REPLACEMENT_CHAR)
+                    .map(|font| (font, REPLACEMENT_CHAR))
+            }) else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some((run_font, run)) if std::ptr::eq(*run_font, font) => run.push(c),
+                _ => runs.push((font, c.to_string())),
+            }
+        }
+        runs
+    }
+
+    /// 그리지 않는 글자 (제어 문자, 폭 없는 공백, 변형 선택자, 피부색)
+    fn is_ignorable(c: char) -> bool {
+        c.is_control()
+            || matches!(
+                c,
+                '\u{200B}'..='\u{200F}'
+                    | '\u{2060}'
+                    | '\u{FE00}'..='\u{FE0F}'
+                    | '\u{1F3FB}'..='\u{1F3FF}'
+                    | '\u{E0100}'..='\u{E01EF}'
+            )
+    }
+
+    /// max_width 를 넘지 않도록 줄을 나눈다.
+    /// - 띄어쓰기에서 먼저 나누고, 한 단어가 너무 길면 글자(음절) 단위로 나눈다.
+    /// - 원래 줄바꿈과 빈 줄은 유지한다.
+    pub fn wrap(&self, text: &str, max_width: f32) -> Vec<String> {
+        let text = text.replace('\r', "").replace('\t', "    ");
+        let mut lines = Vec::new();
+        for paragraph in text.split('\n') {
+            let paragraph = paragraph.trim_end();
+            if paragraph.is_empty() {
+                lines.push(String::new());
+                continue;
+            }
+
+            let mut line = String::new();
+            for word in Self::split_words(paragraph) {
+                let next = format!("{}{}", line, word);
+                if self.text_width(next.trim_end()) <= max_width {
+                    line = next;
+                    continue;
+                }
+                if !line.trim().is_empty() {
+                    lines.push(line.trim_end().to_string());
+                }
+                line = String::new();
+                let word = word.trim_start();
+                if self.text_width(word.trim_end()) <= max_width {
+                    line.push_str(word);
+                    continue;
+                }
+                // 한 줄보다 긴 단어
+                for c in word.chars() {
+                    let mut next = line.clone();
+                    next.push(c);
+                    if !line.is_empty() && self.text_width(next.trim_end()) > max_width {
+                        lines.push(line.trim_end().to_string());
+                        line = String::new();
+                        if c == ' ' {
+                            continue;
+                        }
+                    }
+                    line.push(c);
+                }
+            }
+            if !line.trim().is_empty() {
+                lines.push(line.trim_end().to_string());
+            }
+        }
+        lines
+    }
+
+    /// 단어 뒤의 띄어쓰기를 단어에 붙여서 나눈다.
+    fn split_words(text: &str) -> Vec<&str> {
+        let mut words = Vec::new();
+        let mut start = 0;
+        let mut in_space = false;
+        for (index, c) in text.char_indices() {
+            if c == ' ' {
+                in_space = true;
+            } else if in_space {
+                words.push(&text[start..index]);
+                start = index;
+                in_space = false;
+            }
+        }
+        if start < text.len() {
+            words.push(&text[start..]);
+        }
+        words
+    }
+
+    /// 가장 긴 줄의 폭 (px)
+    pub fn max_width(&self, lines: &[String]) -> f32 {
+        lines.iter().map(|f| self.text_width(f)).fold(0.0, f32::max)
+    }
+
+    /// 한 줄 그리기. y 는 줄의 위쪽
+    /// - 글꼴마다 높이가 달라서 기준 글꼴의 기준선에 맞춘다.
+    pub fn draw_line(&self, img: &mut RgbImage, color: Rgb<u8>, x: i32, y: i32, text: &str) {
+        let ascent = self.font.v_metrics(self.scale).ascent;
+        let mut x_position = x as f32;
+        for (font, run) in self.runs(text) {
+            let y_offset = ascent - font.v_metrics(self.scale).ascent;
+            imageproc::drawing::draw_text_mut(
+                img,
+                color,
+                x_position.round() as i32,
+                (y as f32 + y_offset).round() as i32,
+                self.scale,
+                font,
+                &run,
+            );
+            x_position += self.run_width(font, &run);
+        }
+    }
+}
@@ -1,72 +1,167 @@
 use serde::{Deserialize, Serialize};
 
-use crate::resource::datatypes::ResourceImageResponse;
+use crate::{
+    resource::datatypes::ResourceImageResponse,
+    schema::{extra_fields, null_default, ExtraFields, UnknownFields},
+};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MeInfoResponse {
     pub user: MeInfoUserResponse,
+    #[serde(default, deserialize_with = "null_default")]
     pub children: Vec<MeInfoChildrenResponse>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MeInfoUserResponse {
     pub id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub username: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub r#type: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub country_code: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub phone: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub email: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub description: String,
     pub picture: Option<String>,
+    #[serde(default, deserialize_with = "null_default")]
     pub date_joined: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub subscription: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub subscription_updated_at: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub third_party_consent: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub date_store_allowed: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub is_staff: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub show_change_password: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub use_privacy: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MeInfoChildrenResponse {
     pub id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub created: String,
     pub name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub date_birth: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub gender: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub family_type: String,
     pub picture: Option<ResourceImageResponse>, //
-    pub parent: MeInfoChildParentResponse,      //
+    #[serde(default, deserialize_with = "null_default")]
+    pub parent: MeInfoChildParentResponse, //
+    #[serde(default, deserialize_with = "null_default")]
     pub enrollment: Vec<MeInfoChildEnrollment>, //
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct MeInfoChildParentResponse {
+    #[serde(default, deserialize_with = "null_default")]
     pub id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub r#type: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub name: String,
     pub picture: Option<ResourceImageResponse>,
+    #[serde(default, deserialize_with = "null_default")]
     pub username: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct MeInfoChildEnrollment {
     pub id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub created: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub modified: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub child: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub child_id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub child_name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub child_birth: String,
     pub child_picture: Option<ResourceImageResponse>,
+    #[serde(default, deserialize_with = "null_default")]
     pub parent_name: String,
     pub center_id: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub center_name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub belong_to_class: u64,
+    #[serde(default, deserialize_with = "null_default")]
     pub class_name: String,
+    #[serde(default, deserialize_with = "null_default")]
     pub is_approved: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub removed_child: bool,
+    #[serde(default, deserialize_with = "null_default")]
     pub is_extra_parent: bool,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl UnknownFields for MeInfoResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.user.unknown_fields(&format!("{}.user", path), fields);
+        self.children
+            .unknown_fields(&format!("{}.children", path), fields);
+    }
+}
+
+impl UnknownFields for MeInfoUserResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+    }
+}
+
+impl UnknownFields for MeInfoChildrenResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.picture
+            .unknown_fields(&format!("{}.picture", path), fields);
+        self.parent
+            .unknown_fields(&format!("{}.parent", path), fields);
+        self.enrollment
+            .unknown_fields(&format!("{}.enrollment", path), fields);
+    }
+}
+
+impl UnknownFields for MeInfoChildParentResponse {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.picture
+            .unknown_fields(&format!("{}.picture", path), fields);
+    }
+}
+
+impl UnknownFields for MeInfoChildEnrollment {
+    fn unknown_fields(&self, path: &str, fields: &mut Vec<String>) {
+        extra_fields(path, &self.extra, fields);
+        self.child_picture
+            .unknown_fields(&format!("{}.child_picture", path), fields);
+    }
 }
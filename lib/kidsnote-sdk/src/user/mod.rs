@@ -3,21 +3,57 @@ pub mod datatypes;
 use datatypes::MeInfoResponse;
 use std::sync::{Arc, Mutex};
 
-use crate::{auth::error_types::AuthError, options::KidsnoteOptions};
+use crate::{
+    archive::ResponseArchive,
+    auth::{
+        error_types::{AuthError, AuthErrorCode},
+        KidsnoteAuthSdk,
+    },
+    options::KidsnoteOptions,
+    schema::decode_response,
+};
 
 pub struct KidsnoteUserSdk {
     options: Arc<Mutex<KidsnoteOptions>>,
+    auth: KidsnoteAuthSdk,
+    archive: Option<ResponseArchive>,
 }
 
 impl KidsnoteUserSdk {
-    pub fn new(options: Arc<Mutex<KidsnoteOptions>>) -> KidsnoteUserSdk {
-        Self { options }
+    pub fn new(options: Arc<Mutex<KidsnoteOptions>>, auth: KidsnoteAuthSdk) -> KidsnoteUserSdk {
+        Self {
+            options,
+            auth,
+            archive: None,
+        }
+    }
+
+    /// 응답 원문 보관. None 이면 보관하지 않는다.
+    pub fn set_response_archive(&mut self, archive: Option<ResponseArchive>) {
+        self.archive = archive;
     }
 
+    /// 내정보 조회
+    /// - access token 이 만료되었거나 401 이면 세션을 갱신하고 한 번 더 요청한다.
     pub async fn get_myinfo(&self) -> Result<MeInfoResponse, AuthError> {
-        let options = self.options.lock().unwrap();
-        let access_token = options.get_access_token_or_error()?;
-        let url = format!("{}/v1/me/info/", options.get_host_ref());
+        self.auth.ensure_session().await?;
+        match self.request_myinfo().await {
+            Err(err) if err.code() == Some(AuthErrorCode::Unauthorized) => {
+                log::warn!(target:"auth", "unauthorized. refresh session and retry. {}", err);
+                self.auth.refresh_session().await?;
+                self.request_myinfo().await
+            }
+            result => result,
+        }
+    }
+
+    async fn request_myinfo(&self) -> Result<MeInfoResponse, AuthError> {
+        let (access_token, url) = {
+            let options = self.options.lock().unwrap();
+            let access_token = options.get_access_token_or_error()?;
+            let url = format!("{}/v1/me/info/", options.get_host_ref());
+            (access_token, url)
+        };
 
         let client = reqwest::Client::new();
         let response = client
@@ -35,19 +71,21 @@ impl KidsnoteUserSdk {
         match response {
             Ok(response) => {
                 if response.status().is_success() {
-                    match response.json::<MeInfoResponse>().await {
-                        Ok(result) => Ok(result),
-                        Err(e) => {
-                            //log::error!("update_world_multilingual error: {}", e);
-                            Err(AuthError::GeneralErrorStr(format!("parse error. {:?}", e)))
-                        }
+                    let body = response
+                        .bytes()
+                        .await
+                        .map_err(|e| AuthError::GeneralErrorStr(format!("parse error. {:?}", e)))?;
+                    if let Some(archive) = &self.archive {
+                        archive.record(ResponseArchive::ME_INFO, "me_info", &body);
                     }
+                    decode_response::<MeInfoResponse>(ResponseArchive::ME_INFO, &body)
                 } else {
                     //log::error!("update_world_multilingual error: {}", response.status().as_u16());
-                    Err(AuthError::GeneralErrorStr(format!(
-                        "status error. status={:?}",
-                        response.status()
-                    )))
+                    let status = response.status();
+                    Err(AuthError::ErrorWithCodeString(
+                        AuthErrorCode::from_u16(status.as_u16()),
+                        format!("status error. status={:?}", status),
+                    ))
                 }
             }
             Err(e) => {